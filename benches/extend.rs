@@ -0,0 +1,49 @@
+#![feature(test)]
+
+extern crate test;
+
+use std::io::Write;
+
+use divbuf::*;
+use test::Bencher;
+
+const LEN: usize = 1 << 16;
+
+#[bench]
+fn bench_try_extend(bench: &mut Bencher) {
+    let src = vec![0u8; LEN];
+    let dbs = DivBufShared::with_capacity(LEN);
+    let mut dbm = dbs.try_mut().unwrap();
+
+    bench.bytes = LEN as u64;
+    bench.iter(move || {
+        dbm.try_truncate(0).unwrap();
+        dbm.try_extend(src.iter()).unwrap();
+    })
+}
+
+#[bench]
+fn bench_try_extend_from_slice(bench: &mut Bencher) {
+    let src = vec![0u8; LEN];
+    let dbs = DivBufShared::with_capacity(LEN);
+    let mut dbm = dbs.try_mut().unwrap();
+
+    bench.bytes = LEN as u64;
+    bench.iter(move || {
+        dbm.try_truncate(0).unwrap();
+        dbm.try_extend_from_slice(&src).unwrap();
+    })
+}
+
+#[bench]
+fn bench_write_all(bench: &mut Bencher) {
+    let src = vec![0u8; LEN];
+    let dbs = DivBufShared::with_capacity(LEN);
+    let mut dbm = dbs.try_mut().unwrap();
+
+    bench.bytes = LEN as u64;
+    bench.iter(move || {
+        dbm.try_truncate(0).unwrap();
+        dbm.write_all(&src).unwrap();
+    })
+}
@@ -2,7 +2,10 @@
 
 extern crate test;
 
-use std::{collections::hash_map::DefaultHasher, hash::Hash};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
 
 use divbuf::*;
 use test::Bencher;
@@ -18,3 +21,29 @@ fn bench_divbuf_hash(bench: &mut Bencher) {
         db.hash(&mut hasher);
     })
 }
+
+#[bench]
+fn bench_divbuf_rehash(bench: &mut Bencher) {
+    let dbs = DivBufShared::from(vec![0u8; 4096]);
+    let db = dbs.try_const().unwrap();
+
+    bench.bytes = db.len() as u64;
+    bench.iter(move || {
+        let mut hasher = DefaultHasher::new();
+        db.hash(&mut hasher);
+        hasher.finish()
+    })
+}
+
+#[bench]
+fn bench_hasheddivbuf_rehash(bench: &mut Bencher) {
+    let dbs = DivBufShared::from(vec![0u8; 4096]);
+    let hdb = HashedDivBuf::new(dbs.try_const().unwrap());
+
+    bench.bytes = hdb.get_ref().len() as u64;
+    bench.iter(move || {
+        let mut hasher = DefaultHasher::new();
+        hdb.hash(&mut hasher);
+        hasher.finish()
+    })
+}
@@ -1,22 +1,35 @@
 // vim: tw=80
 
+#[cfg(not(feature = "loom"))]
+use std::sync::atomic::{self, AtomicUsize};
 use std::{
     borrow::{Borrow, BorrowMut},
     cmp,
+    collections::hash_map::DefaultHasher,
     convert::TryFrom,
     error,
     fmt::{self, Debug, Formatter},
-    hash,
+    hash::{self, Hasher},
     io,
-    mem,
-    ops,
-    sync::atomic::{
-        self,
-        AtomicUsize,
-        Ordering::{AcqRel, Acquire, Relaxed, Release},
+    iter::FromIterator,
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    ops::{self, Bound, Range, RangeBounds},
+    slice,
+    string,
+    sync::{
+        atomic::Ordering::{self, AcqRel, Acquire, Relaxed, Release},
+        Arc,
+        Mutex,
+        Weak,
     },
 };
 
+#[cfg(feature = "loom")]
+use loom::sync::atomic::{self, AtomicUsize};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 #[cfg(target_pointer_width = "64")]
 const WRITER_SHIFT: usize = 32;
 #[cfg(target_pointer_width = "64")]
@@ -26,6 +39,35 @@ const WRITER_SHIFT: usize = 16;
 #[cfg(target_pointer_width = "32")]
 const READER_MASK: usize = 0xFFFF;
 const ONE_WRITER: usize = 1 << WRITER_SHIFT;
+/// How many bytes of a buffer to print with `Debug`
+const DEBUG_BYTES: usize = 32;
+
+/// Atomically add one reader to an `Inner`'s `accessors` field, returning the
+/// previous value.
+///
+/// Panics rather than allow the reader count to overflow into the writer count.
+fn add_reader(accessors: &AtomicUsize, order: Ordering) -> usize {
+    let mut old = accessors.load(Relaxed);
+    loop {
+        assert!(old & READER_MASK != READER_MASK, "accessor count overflow");
+        match accessors.compare_exchange_weak(old, old + 1, order, Relaxed) {
+            Ok(_) => return old,
+            Err(x) => old = x,
+        }
+    }
+}
+
+/// Returns true if every byte of `s` equals `value`.
+fn is_all(s: &[u8], value: u8) -> bool {
+    // Compare a word at a time where possible
+    let word = u64::from_ne_bytes([value; 8]);
+    let mut words = s.chunks_exact(8);
+    let mut buf = [0u8; 8];
+    words.all(|w| {
+        buf.copy_from_slice(w);
+        u64::from_ne_bytes(buf) == word
+    }) && words.remainder().iter().all(|&b| b == value)
+}
 
 /// DivBuf's error type
 #[derive(Clone, Copy, Debug)]
@@ -39,6 +81,97 @@ impl fmt::Display for Error {
 
 impl error::Error for Error {}
 
+/// Formats the first few bytes of a buffer, eliding the rest
+struct DebugBytes<'a>(&'a [u8]);
+
+impl<'a> Debug for DebugBytes<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        let mut list = f.debug_list();
+        list.entries(self.0.iter().take(DEBUG_BYTES));
+        if self.0.len() > DEBUG_BYTES {
+            list.entry(&format_args!("..."));
+        }
+        list.finish()
+    }
+}
+
+mod private {
+    /// Prevents other crates from implementing `ByteWindow`
+    pub trait Sealed {}
+}
+
+/// A readable window into a buffer, implemented by [`DivBuf`] and
+/// [`DivBufMut`].
+///
+/// Unlike `AsRef<[u8]>`, it can also report where the window lies within the
+/// underlying [`DivBufShared`].  This trait is sealed; it can't be implemented
+/// outside of this crate.
+///
+/// # Examples
+/// ```
+/// # use divbuf::*;
+/// fn describe<W: ByteWindow>(w: &W) -> String {
+///     format!("{} bytes at offset {}", w.len(), w.offset())
+/// }
+///
+/// let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+/// let mut db = dbs.try_const().unwrap();
+/// db.split_to(1);
+/// assert_eq!(describe(&db), "3 bytes at offset 1");
+/// ```
+///
+/// [`DivBuf`]: struct.DivBuf.html
+/// [`DivBufMut`]: struct.DivBufMut.html
+/// [`DivBufShared`]: struct.DivBufShared.html
+pub trait ByteWindow: private::Sealed {
+    /// Returns the window's contents.
+    fn as_bytes(&self) -> &[u8];
+
+    /// Returns true if the window has length 0.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the length of the window, _not_ the underlying storage.
+    fn len(&self) -> usize;
+
+    /// Returns the position of the window's first byte within the underlying
+    /// storage.
+    fn offset(&self) -> usize;
+}
+
+impl private::Sealed for DivBuf {}
+
+impl ByteWindow for DivBuf {
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn offset(&self) -> usize {
+        self.begin
+    }
+}
+
+impl private::Sealed for DivBufMut {}
+
+impl ByteWindow for DivBufMut {
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn offset(&self) -> usize {
+        self.begin
+    }
+}
+
 /// The return type of
 /// [`DivBuf::into_chunks`](struct.DivBuf.html#method.into_chunks)
 // LCOV_EXCL_START
@@ -76,6 +209,109 @@ impl Iterator for Chunks {
     }
 }
 
+impl ExactSizeIterator for Chunks {}
+
+/// The return type of
+/// [`DivBuf::into_indexed_chunks`](struct.DivBuf.html#method.into_indexed_chunks)
+// LCOV_EXCL_START
+#[derive(Debug)]
+pub struct IndexedChunks {
+    chunks: Chunks,
+    offset: usize,
+}
+// LCOV_EXCL_STOP
+
+impl IndexedChunks {
+    fn new(db: DivBuf, chunksize: usize) -> Self {
+        IndexedChunks {
+            chunks: Chunks::new(db, chunksize),
+            offset: 0,
+        }
+    }
+}
+
+impl Iterator for IndexedChunks {
+    type Item = (usize, DivBuf);
+
+    fn next(&mut self) -> Option<(usize, DivBuf)> {
+        let chunk = self.chunks.next()?;
+        let offset = self.offset;
+        self.offset += chunk.len();
+        Some((offset, chunk))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+/// The return type of [`DivBuf::split`](struct.DivBuf.html#method.split)
+// LCOV_EXCL_START
+#[derive(Debug)]
+pub struct Split {
+    /// The unsplit remainder, or `None` once the last piece has been returned
+    db:    Option<DivBuf>,
+    delim: u8,
+}
+// LCOV_EXCL_STOP
+
+impl Split {
+    fn new(db: DivBuf, delim: u8) -> Self {
+        Split {
+            db: Some(db),
+            delim,
+        }
+    }
+}
+
+impl Iterator for Split {
+    type Item = DivBuf;
+
+    fn next(&mut self) -> Option<DivBuf> {
+        let db = self.db.as_mut()?;
+        match db.position(self.delim) {
+            Some(i) => {
+                let mut piece = db.split_to(i + 1);
+                piece.truncate_to(i);
+                Some(piece)
+            }
+            None => self.db.take(),
+        }
+    }
+}
+
+/// The return type of
+/// [`DivBuf::split_inclusive`](struct.DivBuf.html#method.split_inclusive)
+// LCOV_EXCL_START
+#[derive(Debug)]
+pub struct SplitInclusive {
+    db:    DivBuf,
+    delim: u8,
+}
+// LCOV_EXCL_STOP
+
+impl SplitInclusive {
+    fn new(db: DivBuf, delim: u8) -> Self {
+        SplitInclusive { db, delim }
+    }
+}
+
+impl Iterator for SplitInclusive {
+    type Item = DivBuf;
+
+    fn next(&mut self) -> Option<DivBuf> {
+        if self.db.is_empty() {
+            None
+        } else {
+            let at = match self.db.position(self.delim) {
+                Some(i) => i + 1,
+                None => self.db.len(),
+            };
+            Some(self.db.split_to(at))
+        }
+    }
+}
+
 /// The return type of
 /// [`DivBufMut::into_chunks`](struct.DivBufMut.html#method.into_chunks)
 // LCOV_EXCL_START
@@ -113,17 +349,247 @@ impl Iterator for ChunksMut {
     }
 }
 
+impl ExactSizeIterator for ChunksMut {}
+
+/// The return type of
+/// [`DivBufMut::into_chunks_exact`](struct.DivBufMut.html#method.into_chunks_exact)
+// LCOV_EXCL_START
+#[derive(Debug)]
+pub struct ChunksExactMut {
+    db:        DivBufMut,
+    remainder: DivBufMut,
+    chunksize: usize,
+}
+// LCOV_EXCL_STOP
+
+impl ChunksExactMut {
+    fn new(mut db: DivBufMut, chunksize: usize) -> Self {
+        let rem = db.len() % chunksize;
+        let remainder = db.split_off(db.len() - rem);
+        ChunksExactMut {
+            db,
+            remainder,
+            chunksize,
+        }
+    }
+
+    /// Consume the iterator, returning the leftover bytes that don't fill a
+    /// whole chunk.
+    ///
+    /// Any chunks not yet yielded will be dropped.
+    pub fn into_remainder(self) -> DivBufMut {
+        self.remainder
+    }
+
+    /// Returns the leftover bytes that don't fill a whole chunk.
+    ///
+    /// They will never be yielded by the iterator.
+    pub fn remainder(&mut self) -> &mut DivBufMut {
+        &mut self.remainder
+    }
+}
+
+impl Iterator for ChunksExactMut {
+    type Item = DivBufMut;
+
+    fn next(&mut self) -> Option<DivBufMut> {
+        if self.db.is_empty() {
+            None
+        } else {
+            Some(self.db.split_to(self.chunksize))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let c = self.db.len() / self.chunksize;
+        (c, Some(c))
+    }
+}
+
+/// The return type of [`DivBuf::chunks`](struct.DivBuf.html#method.chunks)
 // LCOV_EXCL_START
 #[derive(Debug)]
+pub struct ChunksRef<'a> {
+    db:        &'a DivBuf,
+    chunksize: usize,
+    pos:       usize,
+}
+// LCOV_EXCL_STOP
+
+impl<'a> ChunksRef<'a> {
+    fn new(db: &'a DivBuf, chunksize: usize) -> Self {
+        ChunksRef {
+            db,
+            chunksize,
+            pos: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for ChunksRef<'a> {
+    type Item = DivBuf;
+
+    fn next(&mut self) -> Option<DivBuf> {
+        if self.pos >= self.db.len() {
+            None
+        } else {
+            let end = cmp::min(
+                self.pos.saturating_add(self.chunksize),
+                self.db.len(),
+            );
+            let chunk = self.db.slice(self.pos, end);
+            self.pos = end;
+            Some(chunk)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.db.len() - self.pos;
+        let mut c = remaining / self.chunksize;
+        if remaining % self.chunksize != 0 {
+            c += 1;
+        }
+        (c, Some(c))
+    }
+}
+
 struct Inner {
     vec:       Vec<u8>,
+    /// `vec`'s data pointer, as returned by `Vec::as_mut_ptr`.  All access to
+    /// the contents goes through this, because `Vec::as_ptr`'s pointer must
+    /// never be written through.  `modify_vec` keeps it up to date.
+    ptr:       *mut u8,
+    /// Mirrors `vec.len()`, so it can be read while a `DivBufMut` is
+    /// modifying `vec`.
+    len:       AtomicUsize,
+    /// Mirrors `vec.capacity()`, for the same reason as `len`.
+    capacity:  AtomicUsize,
     /// Stores the number of readers in the low half, and writers in the high
     /// half.
     accessors: AtomicUsize,
-    /// Stores the total number of DivBufShareds owning this Inner
+    /// Stores the total number of DivBufShareds and DivBufInaccessibles
+    /// owning this Inner, plus one if there are any accessors.
     sharers:   AtomicUsize,
+    /// Lets loom detect leaked `Inner`s
+    #[cfg(feature = "loom")]
+    _track:    loom::alloc::Track<()>,
+    /// Wipe `vec` when this `Inner` gets dropped
+    #[cfg(feature = "zeroize")]
+    zeroize:   bool,
+    /// Counts how many times `vec`'s storage has been reallocated
+    #[cfg(any(debug_assertions, feature = "testing"))]
+    reallocs:  AtomicUsize,
+    /// The pool, if any, that `vec` should be returned to when this `Inner`
+    /// gets dropped
+    pool:      Option<Weak<PoolInner>>,
+}
+
+impl Debug for Inner {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        let mut d = f.debug_struct("Inner");
+        d.field("vec", &self.vec)
+            .field("len", &self.len)
+            .field("capacity", &self.capacity)
+            .field("accessors", &self.accessors)
+            .field("sharers", &self.sharers);
+        #[cfg(feature = "zeroize")]
+        d.field("zeroize", &self.zeroize);
+        d.finish()
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        #[cfg(feature = "zeroize")]
+        {
+            if self.zeroize {
+                self.vec.zeroize();
+            }
+        }
+        if let Some(pool) = self.pool.take().and_then(|p| p.upgrade()) {
+            pool.recycle(mem::take(&mut self.vec));
+        }
+    }
+}
+
+// All accessors (`DivBuf`s and `DivBufMut`s) collectively own a single
+// reference in `sharers`.  It gets acquired when `accessors` transitions away
+// from zero, and released when `accessors` transitions back.  That way the
+// decision to free an `Inner` depends on only a single counter, just like
+// `std::sync::Arc`'s strong and weak counts.  Only an owner of a `sharers`
+// reference may cause `accessors` to leave zero.
+impl Inner {
+    /// Try to add a reader, failing if there are any writers.
+    fn try_acquire_reader(&self) -> bool {
+        let old = add_reader(&self.accessors, Acquire);
+        if old >> WRITER_SHIFT != 0 {
+            // Back out.  If the writers were dropped in the meantime, we may
+            // have been the last accessor.  But the caller owns a sharer
+            // reference, so we still won't be the ones to free self.
+            if self.accessors.fetch_sub(1, Release) == 1 {
+                atomic::fence(Acquire);
+                let old_sharers = self.sharers.fetch_sub(1, Release);
+                debug_assert!(old_sharers > 1);
+            }
+            false
+        } else {
+            if old == 0 {
+                self.sharers.fetch_add(1, Relaxed);
+            }
+            true
+        }
+    }
+
+    /// Try to add a writer, failing if there are any other accessors.
+    fn try_acquire_writer(&self) -> bool {
+        self.try_acquire_writers(1)
+    }
+
+    /// Try to add `n` writers at once, failing if there are any other
+    /// accessors.
+    fn try_acquire_writers(&self, n: usize) -> bool {
+        debug_assert!(n > 0 && n <= READER_MASK);
+        if self
+            .accessors
+            .compare_exchange(0, n << WRITER_SHIFT, AcqRel, Acquire)
+            .is_ok()
+        {
+            self.sharers.fetch_add(1, Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Drop one reader of `inner`, freeing it if that was the last reference of
+/// any kind.
+unsafe fn release_reader(inner: *mut Inner) {
+    if (*inner).accessors.fetch_sub(1, Release) == 1 {
+        atomic::fence(Acquire);
+        release_sharer(inner);
+    }
+}
+
+/// Drop one writer of `inner`, freeing it if that was the last reference of
+/// any kind.
+unsafe fn release_writer(inner: *mut Inner) {
+    if (*inner).accessors.fetch_sub(ONE_WRITER, Release) == ONE_WRITER {
+        atomic::fence(Acquire);
+        release_sharer(inner);
+    }
+}
+
+/// Drop one reference from `inner`'s `sharers`, freeing it if that was the
+/// last.
+unsafe fn release_sharer(inner: *mut Inner) {
+    if (*inner).sharers.fetch_sub(1, Release) == 1 {
+        // See the comments in std::sync::Arc::drop for why the fence is
+        // required.
+        atomic::fence(Acquire);
+        drop(Box::from_raw(inner));
+    }
 }
-// LCOV_EXCL_STOP
 
 /// The "entry point" to the `divbuf` crate.
 ///
@@ -131,6 +597,18 @@ struct Inner {
 /// application will typically create an instance of this class for every
 /// independent buffer it wants to manage, and then create child `DivBuf`s or
 /// `DivBufMut`s to access the storage.
+///
+/// `DivBufShared`s compare and hash by content, but only while they are idle.
+/// If either has an active [`DivBufMut`], then its contents can't be read, and
+/// it will only equal another `DivBufShared` for the same storage.  Comparing
+/// or hashing briefly acquires a read lock, so a concurrent `try_mut` may
+/// fail.
+///
+/// Like an `Arc`, cloning a `DivBufShared` creates another owner of the same
+/// storage; no data is copied.  The storage lives until every owner and every
+/// child has been dropped.
+///
+/// [`DivBufMut`]: struct.DivBufMut.html
 pub struct DivBufShared {
     inner: *mut Inner,
 }
@@ -172,8 +650,6 @@ pub struct DivBufShared {
 /// [`split_to`]: #method.split_to
 /// [`try_mut`]: #method.try_mut
 /// [`unsplit`]: #method.unsplit
-// LCOV_EXCL_START
-#[derive(Debug)]
 pub struct DivBuf {
     // inner must be *mut just to support the try_mut method
     inner: *mut Inner,
@@ -181,7 +657,6 @@ pub struct DivBuf {
     begin: usize,
     len:   usize,
 }
-// LCOV_EXCL_STOP
 
 /// Provides read-write access to a buffer
 ///
@@ -215,22 +690,22 @@ pub struct DivBuf {
 /// [`try_extend`]: #method.try_extend
 /// [`reserve`]: #method.reserve
 /// [`try_truncate`]: #method.try_truncate
-// LCOV_EXCL_START
-#[derive(Debug)]
 pub struct DivBufMut {
     inner: *mut Inner,
     // In the future, consider optimizing by replacing begin with a pointer
     begin: usize,
     len:   usize,
 }
-// LCOV_EXCL_STOP
 
 /// Does not offer either read or write access to the data, but can be upgraded
 /// to a buffer that does.
 ///
 /// Useful because it implements `Clone`, and does not block other [`DivBufMut`]
 /// structures from existing.
-#[derive(Debug)]
+///
+/// Two `DivBufInaccessible`s compare equal if they refer to the same window of
+/// the same buffer.  Their contents are never compared, since they can't be
+/// read.
 pub struct DivBufInaccessible {
     inner: *mut Inner,
     // In the future, consider optimizing by replacing begin with a pointer
@@ -238,26 +713,456 @@ pub struct DivBufInaccessible {
     len:   usize,
 }
 
-impl DivBufShared {
-    /// Returns the number of bytes the buffer can hold without reallocating.
-    pub fn capacity(&self) -> usize {
-        let inner = unsafe { &*self.inner };
-        inner.vec.capacity()
-    }
+/// A cursor over a [`DivBuf`], for parsing its contents one byte at a time.
+///
+/// Unlike repeatedly splitting the `DivBuf`, a `DivBufCursor` can move both
+/// forward and backward.
+///
+/// # Examples
+///
+/// ```
+/// # use divbuf::*;
+/// let dbs = DivBufShared::from(vec![1, 2, 3]);
+/// let mut cursor = DivBufCursor::new(dbs.try_const().unwrap());
+/// assert_eq!(cursor.read_u8(), Some(1));
+/// assert_eq!(cursor.peek(), Some(2));
+/// assert_eq!(cursor.remaining(), 2);
+/// ```
+///
+/// [`DivBuf`]: struct.DivBuf.html
+#[derive(Debug)]
+pub struct DivBufCursor {
+    db:  DivBuf,
+    pos: usize,
+}
 
-    /// Returns true if the `DivBufShared` has length 0
-    pub fn is_empty(&self) -> bool {
-        let inner = unsafe { &*self.inner };
-        inner.vec.is_empty()
-    }
+/// A [`DivBuf`] bundled with a precomputed hash of its contents.
+///
+/// A `DivBuf`'s contents can't change for as long as it lives, so its hash can
+/// be computed just once.  That makes `HashedDivBuf` cheaper than `DivBuf` as
+/// a key in a `HashMap` that gets probed repeatedly with the same buffers.
+///
+/// # Examples
+///
+/// ```
+/// # use divbuf::*;
+/// use std::collections::HashSet;
+///
+/// let dbs = DivBufShared::from(vec![1, 2, 3, 1, 2, 3]);
+/// let mut db0 = dbs.try_const().unwrap();
+/// let db1 = db0.split_off(3);
+/// let mut set = HashSet::new();
+/// assert!(set.insert(HashedDivBuf::new(db0)));
+/// assert!(!set.insert(HashedDivBuf::new(db1)));
+/// ```
+///
+/// [`DivBuf`]: struct.DivBuf.html
+#[derive(Clone, Debug)]
+pub struct HashedDivBuf {
+    db:   DivBuf,
+    hash: u64,
+}
 
-    /// Returns the number of bytes contained in this buffer.
-    pub fn len(&self) -> usize {
-        let inner = unsafe { &*self.inner };
-        inner.vec.len()
-    }
+/// Assembles a [`DivBufShared`] from many small pieces.
+///
+/// The pieces are accumulated into a single `Vec` with amortized growth, which
+/// becomes the `DivBufShared`'s storage when the builder is finished.
+///
+/// # Examples
+///
+/// ```
+/// # use divbuf::*;
+/// let mut builder = DivBufBuilder::new();
+/// builder.push_slice(b"Hello");
+/// builder.push_slice(b", world");
+/// let dbs = builder.finish();
+/// assert_eq!(dbs.try_const().unwrap(), b"Hello, world"[..]);
+/// ```
+///
+/// [`DivBufShared`]: struct.DivBufShared.html
+#[derive(Debug, Default)]
+pub struct DivBufBuilder {
+    vec: Vec<u8>,
+}
 
-    #[deprecated(since = "0.3.1", note = "use try_const instead")]
+/// An [`io::Read`] and [`io::Seek`] adapter for a [`DivBuf`].
+///
+/// Created by [`DivBuf::reader`].
+///
+/// [`DivBuf`]: struct.DivBuf.html
+/// [`DivBuf::reader`]: struct.DivBuf.html#method.reader
+/// [`io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+/// [`io::Seek`]: https://doc.rust-lang.org/std/io/trait.Seek.html
+#[derive(Debug)]
+pub struct DivBufReader {
+    db:  DivBuf,
+    pos: usize,
+}
+
+/// A pool of reusable storage for [`DivBufShared`]s.
+///
+/// Every buffer issued by [`get`] remembers its pool.  When the last reference
+/// to that buffer's storage goes away, whether that's the `DivBufShared` itself
+/// or one of its children, the storage gets returned to the pool instead of
+/// being freed.  If the pool has already been dropped, then the storage is
+/// simply freed.
+///
+/// Cloning a `DivBufPool` creates another handle to the same pool.
+///
+/// # Examples
+///
+/// ```
+/// # use divbuf::*;
+/// let pool = DivBufPool::new(4096);
+/// let dbs = pool.get();
+/// assert!(dbs.is_empty());
+/// assert!(dbs.capacity() >= 4096);
+/// drop(dbs);
+/// assert_eq!(pool.idle(), 1);
+/// ```
+///
+/// [`DivBufShared`]: struct.DivBufShared.html
+/// [`get`]: #method.get
+#[derive(Clone)]
+pub struct DivBufPool {
+    inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+    capacity: usize,
+    /// Storage that isn't in use by any buffer
+    free:     Mutex<Vec<Vec<u8>>>,
+}
+
+/// A scoped guard for a [`DivBufMut`], created by
+/// [`DivBufShared::mut_scope`].
+///
+/// It dereferences to a `DivBufMut` spanning the entire buffer, and releases
+/// its write access when dropped.  Unlike a bare `DivBufMut`, it can't outlive
+/// the `DivBufShared` it came from.
+///
+/// [`DivBufMut`]: struct.DivBufMut.html
+/// [`DivBufShared::mut_scope`]: struct.DivBufShared.html#method.mut_scope
+#[derive(Debug)]
+pub struct MutGuard<'a> {
+    dbm:     DivBufMut,
+    _shared: PhantomData<&'a DivBufShared>,
+}
+
+impl DivBufShared {
+    /// Returns the number of bytes the buffer can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        // Don't borrow the whole Inner, because a DivBufMut might be modifying
+        // its vec.
+        let capacity = unsafe { &(*self.inner).capacity };
+        capacity.load(Relaxed)
+    }
+
+    /// Replaces the buffer's contents with a copy of `src`, reusing the existing
+    /// allocation if it is large enough.
+    ///
+    /// Will fail if there are any [`DivBuf`] or [`DivBufMut`] objects
+    /// referring to this buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::with_capacity(64);
+    /// dbs.copy_from_slice(&b"abc"[..]).unwrap();
+    /// assert_eq!(dbs.try_const().unwrap(), b"abc"[..]);
+    /// assert_eq!(dbs.capacity(), 64);
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    /// [`DivBufMut`]: struct.DivBufMut.html
+    pub fn copy_from_slice(&self, src: &[u8]) -> Result<(), Error> {
+        let mut dbm = self.try_mut().map_err(|_| {
+            Error("Cannot copy while DivBufs or DivBufMuts are active")
+        })?;
+        dbm.try_truncate(0)?;
+        dbm.try_extend(src)
+    }
+
+    /// Creates a new `DivBufShared` from an iterator of known length.
+    ///
+    /// The storage is allocated just once, with room for exactly `iter.len()`
+    /// bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from_exact_iter((0..4).map(|x| x * 2));
+    /// assert_eq!(dbs.try_const().unwrap(), [0, 2, 4, 6][..]);
+    /// ```
+    pub fn from_exact_iter<I>(iter: I) -> Self
+    where
+        I: ExactSizeIterator<Item = u8>,
+    {
+        let mut v = Vec::with_capacity(iter.len());
+        v.extend(iter);
+        Self::from(v)
+    }
+
+    /// Creates a new `DivBufShared` from a `Vec`, first extending the `Vec` to
+    /// its full capacity with copies of `fill`.
+    ///
+    /// This is useful for turning a large, reusable allocation into a buffer
+    /// that can be read into, without resorting to [`uninitialized`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let mut v = Vec::with_capacity(4);
+    /// v.push(1);
+    /// let dbs = DivBufShared::from_vec_filled(v, 0);
+    /// assert_eq!(dbs.len(), dbs.capacity());
+    /// assert_eq!(dbs.try_const().unwrap()[0], 1);
+    /// ```
+    ///
+    /// [`uninitialized`]: #method.uninitialized
+    pub fn from_vec_filled(mut v: Vec<u8>, fill: u8) -> Self {
+        let capacity = v.capacity();
+        v.resize(capacity, fill);
+        Self::from(v)
+    }
+
+    /// Creates a new `DivBufShared` from the first `len` bytes of a `Vec`.
+    ///
+    /// The remaining bytes are discarded, but the `Vec`'s allocation is kept
+    /// as spare capacity, so nothing is reallocated or copied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is greater than the `Vec`'s length.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let v = vec![1, 2, 3, 4, 5, 6];
+    /// let capacity = v.capacity();
+    /// let dbs = DivBufShared::from_vec_prefix(v, 2);
+    /// assert_eq!(dbs.len(), 2);
+    /// assert_eq!(dbs.capacity(), capacity);
+    /// assert_eq!(dbs.try_const().unwrap(), [1, 2][..]);
+    /// ```
+    pub fn from_vec_prefix(mut v: Vec<u8>, len: usize) -> Self {
+        assert!(len <= v.len(), "Can't take a prefix past the end");
+        v.truncate(len);
+        Self::from(v)
+    }
+
+    /// Returns true if the `DivBufShared` has length 0
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if nothing else refers to this `DivBufShared`'s storage.
+    ///
+    /// That is, there are no [`DivBuf`]s, [`DivBufMut`]s,
+    /// [`DivBufInaccessible`]s, or other clones of this `DivBufShared`, so
+    /// [`try_into_idle`] would succeed.  This is stricter than
+    /// [`is_writable`], which ignores `DivBufInaccessible`s and clones.  The
+    /// result is only a snapshot: another thread may create or drop a child
+    /// at any time, so it can't be relied upon without external
+    /// synchronization.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let dbi = dbs.try_const().unwrap().clone_inaccessible();
+    /// assert!(!dbs.is_idle());
+    /// drop(dbi);
+    /// assert!(dbs.is_idle());
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    /// [`DivBufInaccessible`]: struct.DivBufInaccessible.html
+    /// [`DivBufMut`]: struct.DivBufMut.html
+    /// [`is_writable`]: #method.is_writable
+    /// [`try_into_idle`]: #method.try_into_idle
+    pub fn is_idle(&self) -> bool {
+        let sharers = unsafe { &(*self.inner).sharers };
+        sharers.load(Acquire) == 1
+    }
+
+    /// Returns true if there are no [`DivBuf`]s or [`DivBufMut`]s, so
+    /// [`try_mut`] would succeed.
+    ///
+    /// [`DivBufInaccessible`]s don't count, since they don't block writers.
+    /// Like [`is_idle`], the result is only a snapshot: another thread may
+    /// create a `DivBuf` or `DivBufMut` before the caller can call `try_mut`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let db = dbs.try_const().unwrap();
+    /// assert!(!dbs.is_writable());
+    /// drop(db);
+    /// assert!(dbs.is_writable());
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    /// [`DivBufInaccessible`]: struct.DivBufInaccessible.html
+    /// [`DivBufMut`]: struct.DivBufMut.html
+    /// [`is_idle`]: #method.is_idle
+    /// [`try_mut`]: #method.try_mut
+    pub fn is_writable(&self) -> bool {
+        let accessors = unsafe { &(*self.inner).accessors };
+        accessors.load(Acquire) == 0
+    }
+
+    /// Returns the number of bytes contained in this buffer.
+    pub fn len(&self) -> usize {
+        let len = unsafe { &(*self.inner).len };
+        len.load(Relaxed)
+    }
+
+    /// Try to create a [`MutGuard`] for the entirety of this buffer.
+    ///
+    /// Like [`try_mut`], this will fail if there are any [`DivBuf`] or
+    /// [`DivBufMut`] objects referring to this buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// match dbs.mut_scope() {
+    ///     Ok(mut guard) => guard[0] = 0,
+    ///     Err(e) => panic!("{}", e),
+    /// }
+    /// assert_eq!(dbs.try_const().unwrap(), [0, 2, 3][..]);
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    /// [`DivBufMut`]: struct.DivBufMut.html
+    /// [`MutGuard`]: struct.MutGuard.html
+    /// [`try_mut`]: #method.try_mut
+    pub fn mut_scope(&self) -> Result<MutGuard<'_>, Error> {
+        self.try_mut().map(|dbm| MutGuard {
+            dbm,
+            _shared: PhantomData,
+        })
+    }
+
+    /// Creates a new `DivBufShared` whose storage will be overwritten with
+    /// zeros when it is finally freed.
+    ///
+    /// The wipe happens only once the `DivBufShared` and all of its children
+    /// have been dropped.  Note that it can't cover any old copies of the
+    /// data left behind if the storage is reallocated, for example by
+    /// extending a `DivBufMut` beyond the buffer's capacity.  Nor does it
+    /// cover a `Vec` that has been extracted with `try_into` or
+    /// [`DivBuf::into_vec`].  But [`try_into_idle`] does wipe the storage.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::new_zeroizing(b"hunter2".to_vec());
+    /// assert_eq!(dbs.try_const().unwrap(), b"hunter2"[..]);
+    /// ```
+    ///
+    /// [`DivBuf::into_vec`]: struct.DivBuf.html#method.into_vec
+    /// [`try_into_idle`]: #method.try_into_idle
+    #[cfg(feature = "zeroize")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+    pub fn new_zeroizing(v: Vec<u8>) -> Self {
+        let dbs = Self::from(v);
+        // Nobody else can have access to the Inner yet.
+        unsafe { (*dbs.inner).zeroize = true };
+        dbs
+    }
+
+    /// Returns the number of times that the buffer's storage has been
+    /// reallocated.
+    ///
+    /// Splitting or slicing a buffer never reallocates it, but extending it
+    /// beyond its capacity does.  This is intended for tests that need to
+    /// assert that no copies took place.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::with_capacity(4);
+    /// let mut dbm = dbs.try_mut().unwrap();
+    /// dbm.extend(&[1, 2, 3, 4]);
+    /// let _ = dbm.split_off(2);
+    /// assert_eq!(dbs.realloc_count(), 0);
+    /// ```
+    #[cfg(feature = "testing")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+    pub fn realloc_count(&self) -> usize {
+        let reallocs = unsafe { &(*self.inner).reallocs };
+        reallocs.load(Relaxed)
+    }
+
+    /// Truncates the buffer to length 0, retaining its capacity.
+    ///
+    /// This is equivalent to `resize(0, 0)`.  Will fail if there are any
+    /// [`DivBuf`] or [`DivBufMut`] objects referring to this buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let capacity = dbs.capacity();
+    /// dbs.reset().unwrap();
+    /// assert!(dbs.is_empty());
+    /// assert_eq!(dbs.capacity(), capacity);
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    /// [`DivBufMut`]: struct.DivBufMut.html
+    pub fn reset(&self) -> Result<(), Error> {
+        let mut dbm = self.try_mut().map_err(|_| {
+            Error("Cannot reset while DivBufs or DivBufMuts are active")
+        })?;
+        dbm.try_truncate(0)
+    }
+
+    /// Resizes the buffer in-place so that its length equals `new_len`.
+    ///
+    /// If `new_len` is greater than the current length, then the buffer will
+    /// be extended by the difference, with each new byte set to `value`.  If
+    /// `new_len` is less, then the buffer is simply truncated.  Will fail if
+    /// there are any [`DivBuf`] or [`DivBufMut`] objects referring to this
+    /// buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2]);
+    /// dbs.resize(4, 0).unwrap();
+    /// assert_eq!(dbs.try_const().unwrap(), [1, 2, 0, 0][..]);
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    /// [`DivBufMut`]: struct.DivBufMut.html
+    pub fn resize(&self, new_len: usize, value: u8) -> Result<(), Error> {
+        let mut dbm = self.try_mut().map_err(|_| {
+            Error("Cannot resize while DivBufs or DivBufMuts are active")
+        })?;
+        dbm.try_resize(new_len, value)
+    }
+
+    /// Returns the number of bytes that can be appended to the buffer without
+    /// reallocating.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::with_capacity(64);
+    /// dbs.try_mut().unwrap().extend(&[1, 2, 3]);
+    /// assert_eq!(dbs.spare_capacity(), dbs.capacity() - 3);
+    /// ```
+    pub fn spare_capacity(&self) -> usize {
+        // A DivBufMut could be extending the buffer concurrently, so the two
+        // values might not be consistent with each other.
+        self.capacity().saturating_sub(self.len())
+    }
+
+    #[deprecated(since = "0.3.1", note = "use try_const instead")]
     #[doc(hidden)]
     pub fn r#try(&self) -> Result<DivBuf, Error> {
         self.try_const()
@@ -278,16 +1183,83 @@ impl DivBufShared {
     /// [`DivBufMut`]: struct.DivBufMut.html
     pub fn try_const(&self) -> Result<DivBuf, Error> {
         let inner = unsafe { &*self.inner };
-        if inner.accessors.fetch_add(1, Acquire) >> WRITER_SHIFT != 0 {
-            inner.accessors.fetch_sub(1, Relaxed);
-            Err(Error("Cannot create a DivBuf when DivBufMuts are active"))
-        } else {
+        if inner.try_acquire_reader() {
             let l = inner.vec.len();
             Ok(DivBuf {
                 inner: self.inner,
                 begin: 0,
                 len:   l,
             })
+        } else {
+            Err(Error("Cannot create a DivBuf when DivBufMuts are active"))
+        }
+    }
+
+    /// Try to create a read-only [`DivBuf`] that refers to elements
+    /// `[begin, end)` of this buffer.
+    ///
+    /// Like [`try_const`], will fail if there are any [`DivBufMut`] objects
+    /// referring to this buffer.  It will also fail if the range lies outside
+    /// of the buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let db = dbs.try_const_range(1, 4).unwrap();
+    /// assert_eq!(db, [2, 3, 4][..]);
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    /// [`DivBufMut`]: struct.DivBufMut.html
+    /// [`try_const`]: #method.try_const
+    pub fn try_const_range(
+        &self,
+        begin: usize,
+        end: usize,
+    ) -> Result<DivBuf, Error> {
+        let mut db = self.try_const()?;
+        if begin <= end && end <= db.len {
+            db.begin = begin;
+            db.len = end - begin;
+            Ok(db)
+        } else {
+            Err(Error("Range out of bounds"))
+        }
+    }
+
+    /// Drop this `DivBufShared` and free its storage, but only if nothing else
+    /// refers to that storage.
+    ///
+    /// Normally, dropping a `DivBufShared` while its children are still alive
+    /// merely defers freeing the storage until the last of them is gone.  This
+    /// method lets the caller verify that no [`DivBuf`]s, [`DivBufMut`]s, or
+    /// [`DivBufInaccessible`]s remain.  If any do, then `self` is returned
+    /// unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let db = dbs.try_const().unwrap();
+    /// let dbs = dbs.try_into_idle().unwrap_err();
+    /// drop(db);
+    /// assert!(dbs.try_into_idle().is_ok());
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    /// [`DivBufInaccessible`]: struct.DivBufInaccessible.html
+    /// [`DivBufMut`]: struct.DivBufMut.html
+    pub fn try_into_idle(self) -> Result<(), DivBufShared> {
+        let sharers = unsafe { &(*self.inner).sharers };
+        // If there were any accessors, then sharers would be at least 2.  And
+        // with no other sharers, nobody else can create a new reference.
+        if sharers.load(Acquire) == 1 {
+            // Free the storage the normal way, so it gets wiped if need be.
+            drop(self);
+            Ok(())
+        } else {
+            Err(self)
         }
     }
 
@@ -306,11 +1278,7 @@ impl DivBufShared {
     /// [`DivBufMut`]: struct.DivBufMut.html
     pub fn try_mut(&self) -> Result<DivBufMut, Error> {
         let inner = unsafe { &*self.inner };
-        if inner
-            .accessors
-            .compare_exchange(0, ONE_WRITER, AcqRel, Acquire)
-            .is_ok()
-        {
+        if inner.try_acquire_writer() {
             let l = inner.vec.len();
             Ok(DivBufMut {
                 inner: self.inner,
@@ -325,6 +1293,105 @@ impl DivBufShared {
         }
     }
 
+    /// Try to create a mutable [`DivBufMut`] that refers to elements
+    /// `[begin, end)` of this buffer.
+    ///
+    /// Like [`try_mut`], will fail if there are any [`DivBufMut`] or
+    /// [`DivBuf`] objects referring to this buffer.  It will also fail if the
+    /// range lies outside of the buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let mut dbm = dbs.try_mut_range(1, 4).unwrap();
+    /// dbm[0] = 9;
+    /// drop(dbm);
+    /// assert_eq!(dbs.try_const().unwrap(), [1, 9, 3, 4, 5, 6][..]);
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    /// [`DivBufMut`]: struct.DivBufMut.html
+    /// [`try_mut`]: #method.try_mut
+    pub fn try_mut_range(
+        &self,
+        begin: usize,
+        end: usize,
+    ) -> Result<DivBufMut, Error> {
+        let mut dbm = self.try_mut()?;
+        if begin <= end && end <= dbm.len {
+            dbm.begin = begin;
+            dbm.len = end - begin;
+            Ok(dbm)
+        } else {
+            Err(Error("Range out of bounds"))
+        }
+    }
+
+    /// Try to create several mutable `DivBufMut`s at once, one for each of
+    /// `ranges`.
+    ///
+    /// The ranges must lie within the buffer and must not overlap.  Like
+    /// [`try_mut`], will fail if there are any [`DivBufMut`] or [`DivBuf`]
+    /// objects referring to this buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0; 8]);
+    /// let mut dbms = dbs.try_mut_ranges(&[4..8, 0..2]).unwrap();
+    /// dbms[0][0] = 1;
+    /// dbms[1][0] = 2;
+    /// drop(dbms);
+    /// assert_eq!(dbs.try_const().unwrap(), [2, 0, 0, 0, 1, 0, 0, 0][..]);
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    /// [`DivBufMut`]: struct.DivBufMut.html
+    /// [`try_mut`]: #method.try_mut
+    pub fn try_mut_ranges(
+        &self,
+        ranges: &[Range<usize>],
+    ) -> Result<Vec<DivBufMut>, Error> {
+        let mut sorted = ranges.to_vec();
+        sorted.sort_by_key(|r| (r.start, r.end));
+        if sorted.iter().any(|r| r.start > r.end) {
+            return Err(Error("Range start is greater than its end"));
+        }
+        if sorted.windows(2).any(|w| w[0].end > w[1].start) {
+            return Err(Error("Ranges overlap"));
+        }
+        if ranges.is_empty() {
+            return Ok(Vec::new());
+        }
+        if ranges.len() > READER_MASK {
+            return Err(Error("Too many ranges"));
+        }
+        let inner = unsafe { &*self.inner };
+        if !inner.try_acquire_writers(ranges.len()) {
+            return Err(Error(
+                "Cannot create a new DivBufMut when other DivBufs or \
+                 DivBufMuts are active",
+            ));
+        }
+        // Each DivBufMut owns one of the writer references we just acquired,
+        // so dropping them will release the lock.
+        let dbms = ranges
+            .iter()
+            .map(|r| DivBufMut {
+                inner: self.inner,
+                begin: r.start,
+                len:   r.end - r.start,
+            })
+            .collect::<Vec<_>>();
+        // The length can only be checked once no other writer can change it
+        if sorted[sorted.len() - 1].end > inner.vec.len() {
+            Err(Error("Range out of bounds"))
+        } else {
+            Ok(dbms)
+        }
+    }
+
     /// Create a new DivBufShared with an uninitialized buffer of specified
     /// length.
     ///
@@ -352,27 +1419,105 @@ impl DivBufShared {
     pub fn with_capacity(capacity: usize) -> Self {
         Self::from(Vec::with_capacity(capacity))
     }
-}
 
-impl Debug for DivBufShared {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-        let inner = unsafe { &*self.inner };
-        write!(f, "DivBufShared {{ inner: {:?} }}", inner)
+    /// Creates a new `DivBufShared` of length `len`, filled with zeros.
+    ///
+    /// This is the safe alternative to [`uninitialized`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::with_capacity_zeroed(4);
+    /// assert_eq!(dbs.try_const().unwrap(), [0, 0, 0, 0][..]);
+    /// ```
+    ///
+    /// [`uninitialized`]: #method.uninitialized
+    pub fn with_capacity_zeroed(len: usize) -> Self {
+        Self::from(vec![0u8; len])
     }
-}
 
-impl Drop for DivBufShared {
-    fn drop(&mut self) {
-        let inner = unsafe { &*self.inner };
-        if inner.sharers.fetch_sub(1, Release) == 1
-            && inner.accessors.load(Relaxed) == 0
-        {
-            // See the comments in std::sync::Arc::drop for why the fence is
-            // required.
-            atomic::fence(Acquire);
-            unsafe {
-                drop(Box::from_raw(self.inner));
-            }
+    /// Run `f` on the buffer's contents, without creating a named [`DivBuf`].
+    ///
+    /// A temporary `DivBuf` is held only for the duration of `f`.  Will fail
+    /// if there are any [`DivBufMut`] objects referring to this buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+    /// let sum = dbs.with_read(|s| s.iter().map(|&x| u32::from(x)).sum::<u32>());
+    /// assert_eq!(sum.unwrap(), 10);
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    /// [`DivBufMut`]: struct.DivBufMut.html
+    pub fn with_read<F, R>(&self, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&[u8]) -> R,
+    {
+        let db = self.try_const()?;
+        Ok(f(&db))
+    }
+
+    /// Run `f` on a [`DivBufMut`] spanning the entire buffer.
+    ///
+    /// The `DivBufMut` is dropped as soon as `f` returns.  Will fail if there
+    /// are any [`DivBuf`] or `DivBufMut` objects referring to this buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// dbs.with_write(|dbm| dbm.extend(&[4, 5])).unwrap();
+    /// assert_eq!(dbs.try_const().unwrap(), [1, 2, 3, 4, 5][..]);
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    /// [`DivBufMut`]: struct.DivBufMut.html
+    pub fn with_write<F, R>(&self, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut DivBufMut) -> R,
+    {
+        let mut dbm = self.try_mut()?;
+        Ok(f(&mut dbm))
+    }
+}
+
+impl Clone for DivBufShared {
+    fn clone(&self) -> Self {
+        // Don't borrow the whole Inner, because a DivBufMut might be modifying
+        // its vec.
+        let sharers = unsafe { &(*self.inner).sharers };
+        let old = sharers.fetch_add(1, Acquire);
+        debug_assert!(old > 0);
+        DivBufShared { inner: self.inner }
+    }
+}
+
+impl Debug for DivBufShared {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        let inner = unsafe { &*self.inner };
+        write!(f, "DivBufShared {{ inner: {:?} }}", inner)
+    }
+}
+
+impl Drop for DivBufShared {
+    fn drop(&mut self) {
+        unsafe { release_sharer(self.inner) }
+    }
+}
+
+impl Eq for DivBufShared {}
+
+impl hash::Hash for DivBufShared {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        match self.try_const() {
+            Ok(db) => db.hash(state),
+            // Can't read the contents, so fall back to identity
+            Err(_) => (self.inner as usize).hash(state),
         }
     }
 }
@@ -383,14 +1528,41 @@ impl<'a> From<&'a [u8]> for DivBufShared {
     }
 }
 
+impl<'a> From<&'a DivBuf> for DivBufShared {
+    /// Copy the contents of the `DivBuf` into a new, independent
+    /// `DivBufShared`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs0 = DivBufShared::from(vec![1, 2, 3, 4]);
+    /// let db = dbs0.try_const().unwrap().slice(1, 3);
+    /// let dbs1 = DivBufShared::from(&db);
+    /// assert_eq!(dbs1.try_mut().unwrap(), [2, 3][..]);
+    /// ```
+    fn from(src: &'a DivBuf) -> DivBufShared {
+        DivBufShared::from(src.as_ref())
+    }
+}
+
 impl From<Vec<u8>> for DivBufShared {
-    fn from(src: Vec<u8>) -> DivBufShared {
+    fn from(mut src: Vec<u8>) -> DivBufShared {
         let rc = AtomicUsize::new(0);
         let sharers = AtomicUsize::new(1);
         let inner = Box::new(Inner {
+            len: AtomicUsize::new(src.len()),
+            capacity: AtomicUsize::new(src.capacity()),
+            ptr: src.as_mut_ptr(),
             vec: src,
             accessors: rc,
             sharers,
+            #[cfg(feature = "loom")]
+            _track: loom::alloc::Track::new(()),
+            #[cfg(feature = "zeroize")]
+            zeroize: false,
+            #[cfg(any(debug_assertions, feature = "testing"))]
+            reallocs: AtomicUsize::new(0),
+            pool: None,
         });
         DivBufShared {
             inner: Box::into_raw(inner),
@@ -398,6 +1570,42 @@ impl From<Vec<u8>> for DivBufShared {
     }
 }
 
+impl FromIterator<DivBuf> for DivBufShared {
+    /// Concatenate the contents of many `DivBuf`s into a new `DivBufShared`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs0 = DivBufShared::from(vec![1, 2, 3]);
+    /// let dbs1 = DivBufShared::from(vec![4, 5, 6]);
+    /// let pieces = vec![
+    ///     dbs0.try_const().unwrap().slice_to(2),
+    ///     dbs1.try_const().unwrap().slice_from(1),
+    /// ];
+    /// let merged: DivBufShared = pieces.into_iter().collect();
+    /// assert_eq!(merged.try_const().unwrap(), [1, 2, 5, 6][..]);
+    /// ```
+    fn from_iter<I: IntoIterator<Item = DivBuf>>(iter: I) -> Self {
+        let mut v = Vec::new();
+        for db in iter {
+            v.extend_from_slice(&db);
+        }
+        DivBufShared::from(v)
+    }
+}
+
+impl PartialEq for DivBufShared {
+    fn eq(&self, other: &DivBufShared) -> bool {
+        if self.inner == other.inner {
+            return true;
+        }
+        match (self.try_const(), other.try_const()) {
+            (Ok(db0), Ok(db1)) => db0 == db1,
+            _ => false,
+        }
+    }
+}
+
 impl TryFrom<DivBufShared> for Vec<u8> {
     type Error = DivBufShared;
 
@@ -407,6 +1615,12 @@ impl TryFrom<DivBufShared> for Vec<u8> {
     /// `DivBufShared` (`DivBuf`s, `DivBufMut`s, etc), in which case the
     /// `DivBufShared` will be returned unmodified.
     ///
+    /// The storage is handed over as-is.  Even if the `DivBufShared` was
+    /// created by [`DivBufShared::new_zeroizing`], it won't be wiped; that
+    /// becomes the caller's responsibility.  If the `DivBufShared` came from a
+    /// [`DivBufPool`], then the storage is detached from the pool and won't be
+    /// returned to it.
+    ///
     /// # Examples
     ///
     /// ```
@@ -416,11 +1630,13 @@ impl TryFrom<DivBufShared> for Vec<u8> {
     /// let vec: Vec<u8> = dbs.try_into().unwrap();
     /// assert_eq!(vec, vec![1, 2, 3, 4, 5, 6]);
     /// ```
+    ///
+    /// [`DivBufPool`]: struct.DivBufPool.html
+    /// [`DivBufShared::new_zeroizing`]: struct.DivBufShared.html#method.new_zeroizing
     fn try_from(buf: DivBufShared) -> Result<Self, Self::Error> {
         let inner = unsafe { &*buf.inner };
-        if inner.sharers.load(Acquire) == 1
-            && inner.accessors.load(Acquire) == 0
-        {
+        // If there were any accessors, then sharers would be at least 2.
+        if inner.sharers.load(Acquire) == 1 {
             // See the comments in std::sync::Arc::drop for why the fence is
             // required.
             atomic::fence(Acquire);
@@ -431,73 +1647,497 @@ impl TryFrom<DivBufShared> for Vec<u8> {
             Err(buf)
         }
     }
-}
-
-// DivBufShared owns the target of the `inner` pointer, and no method allows
-// that pointer to be mutated.  Atomic refcounts guarantee that no more than one
-// writer at a time can modify `inner`'s contents (as long as DivBufMut is Sync,
-// which it is).  Therefore, DivBufShared is both Send and Sync.
-unsafe impl Send for DivBufShared {}
-unsafe impl Sync for DivBufShared {}
+}
+
+// DivBufShared owns the target of the `inner` pointer, and no method allows
+// that pointer to be mutated.  Atomic refcounts guarantee that no more than one
+// writer at a time can modify `inner`'s contents (as long as DivBufMut is Sync,
+// which it is).  Therefore, DivBufShared is both Send and Sync.
+unsafe impl Send for DivBufShared {}
+unsafe impl Sync for DivBufShared {}
+
+impl DivBuf {
+    /// Returns true if `other` immediately follows `self` in the same buffer.
+    ///
+    /// This is exactly the condition under which [`unsplit`] will succeed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let mut db0 = dbs.try_const().unwrap();
+    /// let db1 = db0.split_off(4);
+    /// assert!(db0.adjacent_to(&db1));
+    /// assert!(!db1.adjacent_to(&db0));
+    /// ```
+    ///
+    /// [`unsplit`]: #method.unsplit
+    pub fn adjacent_to(&self, other: &DivBuf) -> bool {
+        self.inner == other.inner && self.begin + self.len == other.begin
+    }
+
+    /// Iterate over the buffer in `N`-byte arrays, without copying.
+    ///
+    /// This is equivalent to `<[u8]>::array_chunks`.  Any trailing bytes that
+    /// do not fill a whole array are not yielded; use [`as_chunks`] to get
+    /// them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4]);
+    /// let db = dbs.try_const().unwrap();
+    /// let mut iter = db.array_chunks::<2>();
+    /// assert_eq!(iter.next(), Some(&[0, 1]));
+    /// assert_eq!(iter.next(), Some(&[2, 3]));
+    /// assert!(iter.next().is_none());
+    /// ```
+    ///
+    /// [`as_chunks`]: #method.as_chunks
+    pub fn array_chunks<const N: usize>(&self) -> slice::Iter<'_, [u8; N]> {
+        self.as_chunks::<N>().0.iter()
+    }
+
+    /// View the buffer as a slice of `N`-byte arrays, plus a remainder.
+    ///
+    /// This is equivalent to `<[u8]>::as_chunks`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4]);
+    /// let db = dbs.try_const().unwrap();
+    /// let (chunks, rem) = db.as_chunks::<2>();
+    /// assert_eq!(chunks, &[[0, 1], [2, 3]][..]);
+    /// assert_eq!(rem, &[4][..]);
+    /// ```
+    pub fn as_chunks<const N: usize>(&self) -> (&[[u8; N]], &[u8]) {
+        assert!(N != 0, "chunk size must be non-zero");
+        let n = self.len / N;
+        let (head, tail) = self.split_at(n * N);
+        // Safe because [u8; N] has the same alignment as u8, and head holds
+        // exactly n * N bytes.
+        let chunks = unsafe { slice::from_raw_parts(head.as_ptr().cast(), n) };
+        (chunks, tail)
+    }
+
+    /// Consume the `DivBuf`, splitting it into a head and a tail at the given
+    /// index.
+    ///
+    /// The head contains elements `[0, at)` and the tail contains elements
+    /// `[at, self.len)`.  This is equivalent to [`split_off`], but it is
+    /// never ambiguous about which half is which.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is greater than the `DivBuf`'s length.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let db = dbs.try_const().unwrap();
+    /// let (head, tail) = db.bisect(4);
+    /// assert_eq!(head, [1, 2, 3, 4][..]);
+    /// assert_eq!(tail, [5, 6][..]);
+    /// ```
+    ///
+    /// [`split_off`]: #method.split_off
+    pub fn bisect(mut self, at: usize) -> (DivBuf, DivBuf) {
+        let tail = self.split_off(at);
+        (self, tail)
+    }
+
+    /// Break the buffer up into equal sized chunks, without consuming it
+    ///
+    /// Like [`into_chunks`], but borrows `self` instead.  Each chunk is a new
+    /// `DivBuf`, created as if by [`slice`].  If the `DivBuf` is not evenly
+    /// divisible by `size`, then the last chunk will be smaller.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4]);
+    /// let db = dbs.try_const().unwrap();
+    /// let mut iter = db.chunks(3);
+    /// assert_eq!(iter.next().unwrap(), [0, 1, 2][..]);
+    /// assert_eq!(iter.next().unwrap(), [3, 4][..]);
+    /// assert!(iter.next().is_none());
+    /// assert_eq!(db.len(), 5);
+    /// ```
+    ///
+    /// [`into_chunks`]: #method.into_chunks
+    /// [`slice`]: #method.slice
+    pub fn chunks(&self, size: usize) -> ChunksRef<'_> {
+        assert!(size != 0);
+        ChunksRef::new(self, size)
+    }
+
+    /// Create a [`DivBufInaccessible`].
+    ///
+    /// It may later be upgraded to one of the accessible forms.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::with_capacity(4096);
+    /// let db = dbs.try_const().unwrap();
+    /// let _dbi = db.clone_inaccessible();
+    /// ```
+    pub fn clone_inaccessible(&self) -> DivBufInaccessible {
+        let inner = unsafe { &*self.inner };
+        let old = inner.sharers.fetch_add(1, Acquire);
+        debug_assert!(old > 0);
+        DivBufInaccessible {
+            inner: self.inner,
+            begin: self.begin,
+            len:   self.len,
+        }
+    }
+
+    /// Returns the number of leading bytes that this `DivBuf` has in common
+    /// with `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs0 = DivBufShared::from(&b"abcdef"[..]);
+    /// let dbs1 = DivBufShared::from(&b"abcxyz"[..]);
+    /// let db0 = dbs0.try_const().unwrap();
+    /// let db1 = dbs1.try_const().unwrap();
+    /// assert_eq!(db0.common_prefix_len(&db1), 3);
+    /// ```
+    pub fn common_prefix_len(&self, other: &DivBuf) -> usize {
+        self.iter()
+            .zip(other.iter())
+            .take_while(|(a, b)| a == b)
+            .count()
+    }
+
+    /// Returns the number of set bits in the `DivBuf`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0b1010_0000, 0xff]);
+    /// let db = dbs.try_const().unwrap();
+    /// assert_eq!(db.count_ones(), 10);
+    /// ```
+    pub fn count_ones(&self) -> u64 {
+        // Counting a word at a time is much faster than a byte at a time
+        let (words, rem) = self.as_chunks::<8>();
+        let w: u64 = words
+            .iter()
+            .map(|w| u64::from(u64::from_ne_bytes(*w).count_ones()))
+            .sum();
+        let r: u64 = rem.iter().map(|b| u64::from(b.count_ones())).sum();
+        w + r
+    }
+
+    /// Returns the number of unset bits in the `DivBuf`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0b1010_0000, 0xff]);
+    /// let db = dbs.try_const().unwrap();
+    /// assert_eq!(db.count_zeros(), 6);
+    /// ```
+    pub fn count_zeros(&self) -> u64 {
+        self.len as u64 * 8 - self.count_ones()
+    }
+
+    /// Checks that the `DivBuf`'s contents are an ASCII case-insensitive
+    /// match for `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(&b"Content-Length"[..]);
+    /// let db = dbs.try_const().unwrap();
+    /// assert!(db.eq_ignore_ascii_case(b"content-length"));
+    /// ```
+    pub fn eq_ignore_ascii_case(&self, other: &[u8]) -> bool {
+        <[u8]>::eq_ignore_ascii_case(self, other)
+    }
+
+    /// Break the buffer up into equal sized chunks
+    ///
+    /// Returns an interator which will yield equal sized chunks as smaller
+    /// `DivBuf`s.  If the `DivBuf` is not evenly divisible by `size`, then the
+    /// last chunk will be smaller.  This method is based on
+    /// `slice::chunks`, but with a few key differences:
+    ///
+    /// - It consumes `self`
+    /// - Yields smaller `DivBuf`s, not slices
+    /// - Yields owned objects, not references
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5, 6, 7]);
+    /// let db = dbs.try_const().unwrap();
+    /// let mut iter = db.into_chunks(3);
+    /// assert_eq!(&iter.next().unwrap()[..], &[0, 1, 2][..]);
+    /// assert_eq!(&iter.next().unwrap()[..], &[3, 4, 5][..]);
+    /// assert_eq!(&iter.next().unwrap()[..], &[6, 7][..]);
+    /// assert!(&iter.next().is_none())
+    /// ```
+    pub fn into_chunks(self, size: usize) -> Chunks {
+        assert!(size != 0);
+        Chunks::new(self, size)
+    }
+
+    /// Break the buffer up into equal sized chunks, along with each chunk's
+    /// offset.
+    ///
+    /// Like [`into_chunks`], but each chunk is paired with its position
+    /// relative to the start of the original `DivBuf`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4]);
+    /// let db = dbs.try_const().unwrap();
+    /// let mut iter = db.into_indexed_chunks(2);
+    /// assert_eq!(iter.next().unwrap().0, 0);
+    /// assert_eq!(iter.next().unwrap().0, 2);
+    /// let (offset, chunk) = iter.next().unwrap();
+    /// assert_eq!(offset, 4);
+    /// assert_eq!(chunk, [4][..]);
+    /// assert!(iter.next().is_none());
+    /// ```
+    ///
+    /// [`into_chunks`]: #method.into_chunks
+    pub fn into_indexed_chunks(self, size: usize) -> IndexedChunks {
+        assert!(size != 0);
+        IndexedChunks::new(self, size)
+    }
+
+    /// Attempt to extract the owned storage from a `DivBuf`, without copying.
+    ///
+    /// This will only succeed if the `DivBuf` spans the entire buffer, and is
+    /// the only living reference to it: there must be no other `DivBuf`s, nor
+    /// any `DivBufShared` or `DivBufInaccessible`.  Otherwise, `self` will be
+    /// returned unmodified.
+    ///
+    /// The storage is handed over as-is.  Even if the buffer was created by
+    /// [`DivBufShared::new_zeroizing`], it won't be wiped; that becomes the
+    /// caller's responsibility.  If the buffer came from a [`DivBufPool`], then
+    /// the storage is detached from the pool and won't be returned to it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let db = dbs.try_const().unwrap();
+    /// // Fails, because dbs still refers to the storage
+    /// let db = db.into_vec().unwrap_err();
+    /// drop(dbs);
+    /// assert_eq!(db.into_vec().unwrap(), vec![1, 2, 3]);
+    /// ```
+    ///
+    /// [`DivBufPool`]: struct.DivBufPool.html
+    /// [`DivBufShared::new_zeroizing`]: struct.DivBufShared.html#method.new_zeroizing
+    pub fn into_vec(self) -> Result<Vec<u8>, DivBuf> {
+        let (accessors, sharers, len) = unsafe {
+            (
+                &(*self.inner).accessors,
+                &(*self.inner).sharers,
+                &(*self.inner).len,
+            )
+        };
+        // With no other accessors or sharers, nobody else can create a new
+        // reference to the Inner, so none of these values can change.
+        if accessors.load(Acquire) == 1
+            && sharers.load(Acquire) == 1
+            && self.begin == 0
+            && self.len == len.load(Relaxed)
+        {
+            // See the comments in std::sync::Arc::drop for why the fence is
+            // required.
+            atomic::fence(Acquire);
+            let mut inner_box = unsafe { Box::from_raw(self.inner) };
+            mem::forget(self);
+            Ok(mem::take(&mut inner_box.vec))
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Returns true if every byte in the `DivBuf` equals `value`.
+    ///
+    /// This is vacuously true for an empty `DivBuf`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0, 0, 0, 1]);
+    /// let mut db = dbs.try_const().unwrap();
+    /// assert!(!db.is_all(0));
+    /// let _ = db.split_off(3);
+    /// assert!(db.is_all(0));
+    /// ```
+    pub fn is_all(&self, value: u8) -> bool {
+        is_all(self, value)
+    }
+
+    /// Returns true if the `DivBuf` has length 0
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if the `DivBuf` extends to the end of the `DivBufShared`.
+    ///
+    /// Only a terminal buffer can be extended, so this predicts whether a
+    /// `DivBufMut` created by [`try_mut`] could grow.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+    /// let mut db0 = dbs.try_const().unwrap();
+    /// let db1 = db0.split_off(2);
+    /// assert!(!db0.is_terminal());
+    /// assert!(db1.is_terminal());
+    /// ```
+    ///
+    /// [`try_mut`]: #method.try_mut
+    pub fn is_terminal(&self) -> bool {
+        let len = unsafe { &(*self.inner).len };
+        self.begin + self.len == len.load(Relaxed)
+    }
+
+    /// Returns an iterator over the bytes of the `DivBuf`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let db = dbs.try_const().unwrap();
+    /// assert_eq!(db.iter().map(|&x| u32::from(x)).sum::<u32>(), 6);
+    /// ```
+    pub fn iter(&self) -> slice::Iter<'_, u8> {
+        self.as_ref().iter()
+    }
+
+    /// Get a pointer to the first byte of this `DivBuf`.
+    ///
+    /// Only the `Vec` header gets borrowed, never its contents, so that slices
+    /// of sibling buffers don't invalidate each other under Stacked Borrows.
+    fn window_ptr(&self) -> *const u8 {
+        let vec = unsafe { &(*self.inner).vec };
+        assert!(self.begin + self.len <= vec.len());
+        unsafe { (*self.inner).ptr.add(self.begin) }
+    }
+
+    /// Consume the `DivBuf`, returning a `'static` slice of its contents.
+    ///
+    /// Like `Vec::leak`, this deliberately leaks memory: the `DivBuf`'s read
+    /// lock is never released, so the underlying storage will never be freed,
+    /// nor will it ever be writable again, even after the `DivBufShared` is
+    /// dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// # #[cfg(not(miri))] { // Miri would rightly complain about the leak
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let s: &'static [u8] = dbs.try_const().unwrap().leak();
+    /// drop(dbs);
+    /// assert_eq!(s, &[1, 2, 3][..]);
+    /// # }
+    /// ```
+    pub fn leak(self) -> &'static [u8] {
+        let s = unsafe { slice::from_raw_parts(self.window_ptr(), self.len) };
+        mem::forget(self);
+        s
+    }
+
+    /// Get the length of this `DivBuf`, _not_ the underlying storage
+    pub fn len(&self) -> usize {
+        self.len
+    }
 
-impl DivBuf {
-    /// Create a [`DivBufInaccessible`].
+    /// Returns a new `Vec` holding the result of applying `f` to each byte of
+    /// the `DivBuf`.
     ///
-    /// It may later be upgraded to one of the accessible forms.
+    /// This is the read-only counterpart of [`DivBufMut::map_bytes`].  The
+    /// `DivBuf` itself is unchanged.
     ///
     /// # Examples
     /// ```
     /// # use divbuf::*;
-    /// let dbs = DivBufShared::with_capacity(4096);
+    /// let dbs = DivBufShared::from(&b"abc"[..]);
     /// let db = dbs.try_const().unwrap();
-    /// let _dbi = db.clone_inaccessible();
+    /// assert_eq!(db.map_collect(|x| x.to_ascii_uppercase()), b"ABC");
+    /// assert_eq!(db, b"abc"[..]);
     /// ```
-    pub fn clone_inaccessible(&self) -> DivBufInaccessible {
-        let inner = unsafe { &*self.inner };
-        let old = inner.sharers.fetch_add(1, Acquire);
-        debug_assert!(old > 0);
-        DivBufInaccessible {
-            inner: self.inner,
-            begin: self.begin,
-            len:   self.len,
-        }
+    ///
+    /// [`DivBufMut::map_bytes`]: struct.DivBufMut.html#method.map_bytes
+    pub fn map_collect<F: FnMut(u8) -> u8>(&self, mut f: F) -> Vec<u8> {
+        self.iter().map(|&x| f(x)).collect()
     }
 
-    /// Break the buffer up into equal sized chunks
+    /// Returns the index of the first occurrence of `byte` in the `DivBuf`.
     ///
-    /// Returns an interator which will yield equal sized chunks as smaller
-    /// `DivBuf`s.  If the `DivBuf` is not evenly divisible by `size`, then the
-    /// last chunk will be smaller.  This method is based on
-    /// `slice::chunks`, but with a few key differences:
-    ///
-    /// - It consumes `self`
-    /// - Yields smaller `DivBuf`s, not slices
-    /// - Yields owned objects, not references
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(&b"key=value=x"[..]);
+    /// let db = dbs.try_const().unwrap();
+    /// assert_eq!(db.position(b'='), Some(3));
+    /// assert_eq!(db.position(b';'), None);
+    /// ```
+    pub fn position(&self, byte: u8) -> Option<usize> {
+        self.iter().position(|&b| b == byte)
+    }
+
+    /// Returns the index of the last occurrence of `byte` in the `DivBuf`.
     ///
     /// # Examples
     /// ```
     /// # use divbuf::*;
-    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5, 6, 7]);
+    /// let dbs = DivBufShared::from(&b"key=value=x"[..]);
     /// let db = dbs.try_const().unwrap();
-    /// let mut iter = db.into_chunks(3);
-    /// assert_eq!(&iter.next().unwrap()[..], &[0, 1, 2][..]);
-    /// assert_eq!(&iter.next().unwrap()[..], &[3, 4, 5][..]);
-    /// assert_eq!(&iter.next().unwrap()[..], &[6, 7][..]);
-    /// assert!(&iter.next().is_none())
+    /// assert_eq!(db.rposition(b'='), Some(9));
+    /// assert_eq!(db.rposition(b';'), None);
     /// ```
-    pub fn into_chunks(self, size: usize) -> Chunks {
-        assert!(size != 0);
-        Chunks::new(self, size)
+    pub fn rposition(&self, byte: u8) -> Option<usize> {
+        self.iter().rposition(|&b| b == byte)
     }
 
-    /// Returns true if the `DivBuf` has length 0
-    pub fn is_empty(&self) -> bool {
-        self.len == 0
+    /// Returns the capacity of the underlying storage.
+    pub fn shared_capacity(&self) -> usize {
+        let capacity = unsafe { &(*self.inner).capacity };
+        capacity.load(Relaxed)
     }
 
-    /// Get the length of this `DivBuf`, _not_ the underlying storage
-    pub fn len(&self) -> usize {
-        self.len
+    /// Returns the length of the underlying storage, _not_ of this `DivBuf`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let db = dbs.try_const().unwrap().slice(1, 4);
+    /// assert_eq!(db.len(), 3);
+    /// assert_eq!(db.shared_len(), 6);
+    /// ```
+    pub fn shared_len(&self) -> usize {
+        let len = unsafe { &(*self.inner).len };
+        len.load(Relaxed)
     }
 
     /// Create a new DivBuf that spans a subset of this one.
@@ -514,7 +2154,10 @@ impl DivBuf {
         assert!(begin <= end);
         assert!(end <= self.len);
         let inner = unsafe { &*self.inner };
-        let old_accessors = inner.accessors.fetch_add(1, Relaxed);
+        // Relaxed is sufficient, as in Arc::clone.  self already holds a
+        // reader reference, so no writer can exist, and the new DivBuf needs
+        // no synchronization that self didn't already have.
+        let old_accessors = add_reader(&inner.accessors, Relaxed);
         debug_assert!(old_accessors & READER_MASK > 0);
         DivBuf {
             inner: self.inner,
@@ -552,6 +2195,51 @@ impl DivBuf {
         self.slice(0, end)
     }
 
+    /// Returns the first byte and a `DivBuf` of the rest, or `None` if the
+    /// `DivBuf` is empty.
+    ///
+    /// Note that this shadows `<[u8]>::split_first`.  Unlike that method, it
+    /// consumes the `DivBuf`, and returns the byte by value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![3, 1, 2, 3]);
+    /// let db = dbs.try_const().unwrap();
+    /// let (len, rest) = db.split_first().unwrap();
+    /// assert_eq!(len, 3);
+    /// assert_eq!(rest, [1, 2, 3][..]);
+    /// ```
+    pub fn split_first(mut self) -> Option<(u8, DivBuf)> {
+        let first = *self.first()?;
+        self.begin += 1;
+        self.len -= 1;
+        Some((first, self))
+    }
+
+    /// Returns the last byte and a `DivBuf` of the rest, or `None` if the
+    /// `DivBuf` is empty.
+    ///
+    /// Note that this shadows `<[u8]>::split_last`.  Unlike that method, it
+    /// consumes the `DivBuf`, and returns the byte by value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 0]);
+    /// let db = dbs.try_const().unwrap();
+    /// let (terminator, rest) = db.split_last().unwrap();
+    /// assert_eq!(terminator, 0);
+    /// assert_eq!(rest, [1, 2, 3][..]);
+    /// ```
+    pub fn split_last(mut self) -> Option<(u8, DivBuf)> {
+        let last = *self.last()?;
+        self.len -= 1;
+        Some((last, self))
+    }
+
     /// Splits the DivBuf into two at the given index.
     ///
     /// Afterwards self contains elements `[0, at)`, and the returned DivBuf
@@ -572,7 +2260,7 @@ impl DivBuf {
     pub fn split_off(&mut self, at: usize) -> DivBuf {
         assert!(at <= self.len, "Can't split past the end");
         let inner = unsafe { &*self.inner };
-        let old_accessors = inner.accessors.fetch_add(1, Relaxed);
+        let old_accessors = add_reader(&inner.accessors, Relaxed);
         debug_assert!(old_accessors & READER_MASK > 0);
         let right_half = DivBuf {
             inner: self.inner,
@@ -583,35 +2271,390 @@ impl DivBuf {
         right_half
     }
 
+    /// Splits the DivBuf into two at the last `align`-aligned boundary within
+    /// it.
+    ///
+    /// Alignment is measured relative to the start of the underlying
+    /// `DivBufShared`, not to memory addresses.  Afterwards, the returned
+    /// `DivBuf` begins at the largest multiple of `align` that does not exceed
+    /// the end of `self`.  If `self` contains no such boundary, then the
+    /// returned `DivBuf` will be empty and `self` will be unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    /// let mut db0 = dbs.try_const().unwrap().slice_from(1);
+    /// let db1 = db0.split_off_aligned(4);
+    /// assert_eq!(db0, [1, 2, 3, 4, 5, 6, 7][..]);
+    /// assert_eq!(db1, [8, 9][..]);
+    /// ```
+    pub fn split_off_aligned(&mut self, align: usize) -> DivBuf {
+        assert!(align.is_power_of_two(), "align must be a power of two");
+        let end = self.begin + self.len;
+        let boundary = end & !(align - 1);
+        let at = if boundary < self.begin {
+            self.len
+        } else {
+            boundary - self.begin
+        };
+        self.split_off(at)
+    }
+
+    /// Break the buffer up into the pieces separated by `delim`.
+    ///
+    /// The delimiters themselves are not included in any piece.  Consecutive
+    /// delimiters, or a delimiter at the beginning or end of the `DivBuf`,
+    /// produce empty pieces, just like `<[u8]>::split`, which this shadows.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(&b"a,b,,c"[..]);
+    /// let db = dbs.try_const().unwrap();
+    /// let mut fields = db.split(b',');
+    /// assert_eq!(fields.next().unwrap(), b"a"[..]);
+    /// assert_eq!(fields.next().unwrap(), b"b"[..]);
+    /// assert_eq!(fields.next().unwrap(), b""[..]);
+    /// assert_eq!(fields.next().unwrap(), b"c"[..]);
+    /// assert!(fields.next().is_none());
+    /// ```
+    pub fn split(self, delim: u8) -> Split {
+        Split::new(self, delim)
+    }
+
+    /// Break the buffer up into pieces that each end with `delim`.
+    ///
+    /// The final piece will lack the delimiter if the `DivBuf` doesn't end
+    /// with one.  An empty `DivBuf` yields no pieces.  Note that this shadows
+    /// `<[u8]>::split_inclusive`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(&b"one\ntwo\nthree"[..]);
+    /// let db = dbs.try_const().unwrap();
+    /// let mut lines = db.split_inclusive(b'\n');
+    /// assert_eq!(lines.next().unwrap(), b"one\n"[..]);
+    /// assert_eq!(lines.next().unwrap(), b"two\n"[..]);
+    /// assert_eq!(lines.next().unwrap(), b"three"[..]);
+    /// assert!(lines.next().is_none());
+    /// ```
+    pub fn split_inclusive(self, delim: u8) -> SplitInclusive {
+        SplitInclusive::new(self, delim)
+    }
+
     /// Splits the DivBuf into two at the given index.
     ///
-    /// Afterwards self contains elements `[at, self.len)`, and the returned
-    /// DivBuf contains elements `[0, at)`.
-    /// This is an O(1) operation.
+    /// Afterwards self contains elements `[at, self.len)`, and the returned
+    /// DivBuf contains elements `[0, at)`.
+    /// This is an O(1) operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let mut db0 = dbs.try_const().unwrap();
+    /// let db1 = db0.split_to(4);
+    /// assert_eq!(db0, [5, 6][..]);
+    /// assert_eq!(db1, [1, 2, 3, 4][..]);
+    /// ```
+    pub fn split_to(&mut self, at: usize) -> DivBuf {
+        assert!(at <= self.len, "Can't split past the end");
+        let inner = unsafe { &*self.inner };
+        let old_accessors = add_reader(&inner.accessors, Relaxed);
+        debug_assert!(old_accessors & READER_MASK > 0);
+        let left_half = DivBuf {
+            inner: self.inner,
+            begin: self.begin,
+            len:   at,
+        };
+        self.begin += at;
+        self.len -= at;
+        left_half
+    }
+
+    /// Copy every `step`th byte, beginning at index `start`, into a new
+    /// `Vec`.
+    ///
+    /// Returns an empty `Vec` if `start` is past the end of the `DivBuf`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// // Interleaved samples from two channels
+    /// let dbs = DivBufShared::from(vec![1, 10, 2, 20, 3, 30]);
+    /// let db = dbs.try_const().unwrap();
+    /// assert_eq!(db.stride(0, 2), vec![1, 2, 3]);
+    /// assert_eq!(db.stride(1, 2), vec![10, 20, 30]);
+    /// ```
+    pub fn stride(&self, start: usize, step: usize) -> Vec<u8> {
+        assert!(step != 0, "step must be non-zero");
+        match self.get(start..) {
+            Some(s) => s.iter().step_by(step).copied().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// If the `DivBuf` begins with `prefix`, advance past it and return true.
+    /// Otherwise, leave the `DivBuf` unchanged and return false.
+    ///
+    /// Note that this shadows `<[u8]>::strip_prefix`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(&b"GET /index.html"[..]);
+    /// let mut db = dbs.try_const().unwrap();
+    /// assert!(!db.strip_prefix(b"POST "));
+    /// assert!(db.strip_prefix(b"GET "));
+    /// assert_eq!(db, b"/index.html"[..]);
+    /// ```
+    pub fn strip_prefix(&mut self, prefix: &[u8]) -> bool {
+        if self.starts_with(prefix) {
+            self.begin += prefix.len();
+            self.len -= prefix.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes and returns the first `n` bytes of the DivBuf.
+    ///
+    /// Afterwards self contains the remaining bytes.  This is the same as
+    /// [`split_to`](#method.split_to).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let mut db0 = dbs.try_const().unwrap();
+    /// let header = db0.take(2);
+    /// assert_eq!(header, [1, 2][..]);
+    /// assert_eq!(db0, [3, 4, 5, 6][..]);
+    /// ```
+    pub fn take(&mut self, n: usize) -> DivBuf {
+        self.split_to(n)
+    }
+
+    /// Copy `N` bytes starting at `offset` into an array.
+    ///
+    /// Returns `None` if the `DivBuf` doesn't contain that many bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0, 0, 1, 2, 3, 4]);
+    /// let db = dbs.try_const().unwrap();
+    /// let x = db.read_array::<4>(2).unwrap();
+    /// assert_eq!(u32::from_be_bytes(x), 0x0102_0304);
+    /// assert!(db.read_array::<4>(3).is_none());
+    /// ```
+    pub fn read_array<const N: usize>(&self, offset: usize) -> Option<[u8; N]> {
+        let end = offset.checked_add(N)?;
+        let src = self.get(offset..end)?;
+        let mut a = [0u8; N];
+        a.copy_from_slice(src);
+        Some(a)
+    }
+
+    /// Split a frame with a 4-byte big-endian length prefix off of the front
+    /// of the `DivBuf`.
+    ///
+    /// Returns the frame's body, and advances `self` past the prefix and body.
+    /// If the `DivBuf` is too short to contain the whole frame, returns `None`
+    /// and leaves `self` unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0, 0, 0, 2, 9, 8, 7]);
+    /// let mut db = dbs.try_const().unwrap();
+    /// let body = db.read_frame_u32_be().unwrap();
+    /// assert_eq!(body, [9, 8][..]);
+    /// assert_eq!(db, [7][..]);
+    /// ```
+    pub fn read_frame_u32_be(&mut self) -> Option<DivBuf> {
+        if self.len < 4 {
+            return None;
+        }
+        let mut prefix = [0u8; 4];
+        prefix.copy_from_slice(&self[..4]);
+        let body_len = usize::try_from(u32::from_be_bytes(prefix)).ok()?;
+        if self.len - 4 < body_len {
+            return None;
+        }
+        self.begin += 4;
+        self.len -= 4;
+        Some(self.split_to(body_len))
+    }
+
+    /// Convert the `DivBuf` into an adapter that implements `io::Read` and
+    /// `io::Seek`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// use std::io::Read;
+    ///
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let mut reader = dbs.try_const().unwrap().reader();
+    /// let mut buf = [0; 2];
+    /// assert_eq!(reader.read(&mut buf[..]).unwrap(), 2);
+    /// assert_eq!(buf, [1, 2]);
+    /// ```
+    pub fn reader(self) -> DivBufReader {
+        DivBufReader { db: self, pos: 0 }
+    }
+
+    /// Copy the contents of the `DivBuf` into a new `Arc<[u8]>`.
+    ///
+    /// The result does not refer to the original `DivBufShared` in any way.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+    /// let arc = dbs.try_const().unwrap().slice(1, 3).to_arc_slice();
+    /// // The copy doesn't hold a read lock
+    /// assert!(dbs.try_mut().is_ok());
+    /// assert_eq!(&arc[..], &[2, 3][..]);
+    /// ```
+    pub fn to_arc_slice(&self) -> Arc<[u8]> {
+        Arc::from(self.as_ref())
+    }
+
+    /// Copy the contents of the `DivBuf` into a new `Box<[u8]>`.
+    ///
+    /// The result does not refer to the original `DivBufShared` in any way.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let boxed = dbs.try_const().unwrap().to_boxed_slice();
+    /// assert_eq!(&boxed[..], &[1, 2, 3][..]);
+    /// ```
+    pub fn to_boxed_slice(&self) -> Box<[u8]> {
+        Box::from(self.as_ref())
+    }
+
+    /// Returns a `DivBuf` with leading and trailing ASCII whitespace removed.
+    ///
+    /// This is like `<[u8]>::trim_ascii`, which it shadows, but returns a
+    /// `DivBuf` instead of a slice.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(&b"\r hello world\n"[..]);
+    /// let db = dbs.try_const().unwrap();
+    /// assert_eq!(db.trim_ascii(), b"hello world"[..]);
+    /// ```
+    pub fn trim_ascii(&self) -> DivBuf {
+        let begin = self
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .unwrap_or(self.len);
+        let end = self
+            .iter()
+            .rposition(|b| !b.is_ascii_whitespace())
+            .map_or(begin, |i| i + 1);
+        self.slice(begin, end)
+    }
+
+    /// Returns a `DivBuf` with trailing ASCII whitespace removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(&b" hello\r\n"[..]);
+    /// let db = dbs.try_const().unwrap();
+    /// assert_eq!(db.trim_ascii_end(), b" hello"[..]);
+    /// ```
+    pub fn trim_ascii_end(&self) -> DivBuf {
+        let end = self
+            .iter()
+            .rposition(|b| !b.is_ascii_whitespace())
+            .map_or(0, |i| i + 1);
+        self.slice_to(end)
+    }
+
+    /// Returns a `DivBuf` with leading ASCII whitespace removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(&b"\t hello "[..]);
+    /// let db = dbs.try_const().unwrap();
+    /// assert_eq!(db.trim_ascii_start(), b"hello "[..]);
+    /// ```
+    pub fn trim_ascii_start(&self) -> DivBuf {
+        let begin = self
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .unwrap_or(self.len);
+        self.slice_from(begin)
+    }
+
+    /// Shortens the `DivBuf`, keeping only elements `[0, at)`.
+    ///
+    /// This is equivalent to calling [`split_off`] and dropping the result,
+    /// but without ever creating a new `DivBuf`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let mut db0 = dbs.try_const().unwrap();
+    /// db0.truncate_to(4);
+    /// assert_eq!(db0, [1, 2, 3, 4][..]);
+    /// ```
+    ///
+    /// [`split_off`]: #method.split_off
+    pub fn truncate_to(&mut self, at: usize) {
+        assert!(at <= self.len, "Can't split past the end");
+        self.len = at;
+    }
+
+    /// Attempt to break the buffer up into equal sized chunks
+    ///
+    /// Like [`into_chunks`], but returns an error along with the original
+    /// `DivBuf`, rather than panicking, if `size` is 0.
     ///
     /// # Examples
-    ///
     /// ```
     /// # use divbuf::*;
-    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
-    /// let mut db0 = dbs.try_const().unwrap();
-    /// let db1 = db0.split_to(4);
-    /// assert_eq!(db0, [5, 6][..]);
-    /// assert_eq!(db1, [1, 2, 3, 4][..]);
+    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5]);
+    /// let db = dbs.try_const().unwrap();
+    /// let (db, _e) = db.try_into_chunks(0).unwrap_err();
+    /// let mut iter = db.try_into_chunks(4).unwrap();
+    /// assert_eq!(&iter.next().unwrap()[..], &[0, 1, 2, 3][..]);
     /// ```
-    pub fn split_to(&mut self, at: usize) -> DivBuf {
-        assert!(at <= self.len, "Can't split past the end");
-        let inner = unsafe { &*self.inner };
-        let old_accessors = inner.accessors.fetch_add(1, Relaxed);
-        debug_assert!(old_accessors & READER_MASK > 0);
-        let left_half = DivBuf {
-            inner: self.inner,
-            begin: self.begin,
-            len:   at,
-        };
-        self.begin += at;
-        self.len -= at;
-        left_half
+    ///
+    /// [`into_chunks`]: #method.into_chunks
+    pub fn try_into_chunks(
+        self,
+        size: usize,
+    ) -> Result<Chunks, (DivBuf, Error)> {
+        if size == 0 {
+            Err((self, Error("Chunk size must be non-zero")))
+        } else {
+            Ok(self.into_chunks(size))
+        }
     }
 
     /// Attempt to upgrade Self to a writable DivBufMut
@@ -662,28 +2705,24 @@ impl DivBuf {
     /// assert_eq!(db0, [1, 2, 3, 4, 5, 6][..]);
     /// ```
     pub fn unsplit(&mut self, other: DivBuf) -> Result<(), DivBuf> {
-        if self.inner != other.inner || (self.begin + self.len) != other.begin {
-            Err(other)
-        } else {
+        if self.adjacent_to(&other) {
             self.len += other.len;
             Ok(())
+        } else {
+            Err(other)
         }
     }
 }
 
 impl AsRef<[u8]> for DivBuf {
     fn as_ref(&self) -> &[u8] {
-        unsafe {
-            let inner = &*self.inner;
-            &inner.vec[self.begin..(self.begin + self.len)][..]
-        }
+        unsafe { slice::from_raw_parts(self.window_ptr(), self.len) }
     }
 }
 
 impl Borrow<[u8]> for DivBuf {
     fn borrow(&self) -> &[u8] {
-        let inner = unsafe { &*self.inner };
-        &inner.vec[..]
+        self.as_ref()
     }
 }
 
@@ -701,10 +2740,7 @@ impl ops::Deref for DivBuf {
     type Target = [u8];
 
     fn deref(&self) -> &[u8] {
-        unsafe {
-            let inner = &*self.inner;
-            &inner.vec[self.begin..(self.begin + self.len)][..]
-        }
+        unsafe { slice::from_raw_parts(self.window_ptr(), self.len) }
     }
 }
 
@@ -714,17 +2750,18 @@ impl Clone for DivBuf {
     }
 }
 
+impl Debug for DivBuf {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("DivBuf")
+            .field("len", &self.len)
+            .field("data", &DebugBytes(self))
+            .finish()
+    }
+}
+
 impl Drop for DivBuf {
     fn drop(&mut self) {
-        let inner = unsafe { &*self.inner };
-        if inner.accessors.fetch_sub(1, Release) == 1
-            && inner.sharers.load(Relaxed) == 0
-        {
-            atomic::fence(Acquire);
-            unsafe {
-                drop(Box::from_raw(self.inner));
-            }
-        }
+        unsafe { release_reader(self.inner) }
     }
 }
 
@@ -754,12 +2791,53 @@ impl PartialEq<[u8]> for DivBuf {
     }
 }
 
+impl<const N: usize> PartialEq<[u8; N]> for DivBuf {
+    fn eq(&self, other: &[u8; N]) -> bool {
+        self.as_ref() == &other[..]
+    }
+}
+
+impl<const N: usize> PartialEq<DivBuf> for [u8; N] {
+    fn eq(&self, other: &DivBuf) -> bool {
+        &self[..] == other.as_ref()
+    }
+}
+
 impl PartialOrd for DivBuf {
     fn partial_cmp(&self, other: &DivBuf) -> Option<cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
+impl TryFrom<DivBuf> for String {
+    type Error = string::FromUtf8Error;
+
+    /// Copy the contents of a `DivBuf` into a new `String`, if they are valid
+    /// UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// use std::convert::TryFrom;
+    /// let dbs = DivBufShared::from(&b"Hello"[..]);
+    /// let s = String::try_from(dbs.try_const().unwrap()).unwrap();
+    /// assert_eq!(s, "Hello");
+    /// ```
+    fn try_from(db: DivBuf) -> Result<Self, Self::Error> {
+        String::try_from(&db)
+    }
+}
+
+impl<'a> TryFrom<&'a DivBuf> for String {
+    type Error = string::FromUtf8Error;
+
+    /// Copy the contents of a `DivBuf` into a new `String`, if they are valid
+    /// UTF-8.
+    fn try_from(db: &'a DivBuf) -> Result<Self, Self::Error> {
+        String::from_utf8(db.to_vec())
+    }
+}
+
 // Atomic refcounts provide shared ownership over the `inner` pointer,
 // guaranteeing that it won't be freed as long as a `DivBuf` exists.  No method
 // allows that pointer to be mutated.  Atomic refcounts also guarantee that no
@@ -769,6 +2847,38 @@ unsafe impl Send for DivBuf {}
 unsafe impl Sync for DivBuf {}
 
 impl DivBufMut {
+    /// View the buffer as a mutable slice of `N`-byte arrays, plus a
+    /// remainder.
+    ///
+    /// This is equivalent to `<[u8]>::as_chunks_mut`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4]);
+    /// let mut dbm = dbs.try_mut().unwrap();
+    /// let (chunks, rem) = dbm.as_chunks_mut::<2>();
+    /// chunks[1] = [6, 7];
+    /// rem[0] = 8;
+    /// assert_eq!(dbm, [0, 1, 6, 7, 8][..]);
+    /// ```
+    pub fn as_chunks_mut<const N: usize>(
+        &mut self,
+    ) -> (&mut [[u8; N]], &mut [u8]) {
+        assert!(N != 0, "chunk size must be non-zero");
+        let n = self.len / N;
+        let (head, tail) = self.split_at_mut(n * N);
+        // Safe because [u8; N] has the same alignment as u8, and head holds
+        // exactly n * N bytes.
+        let chunks =
+            unsafe { slice::from_raw_parts_mut(head.as_mut_ptr().cast(), n) };
+        (chunks, tail)
+    }
+
     /// Create a [`DivBufInaccessible`].
     ///
     /// It may later be upgraded to one of the accessible forms.
@@ -791,15 +2901,74 @@ impl DivBufMut {
         }
     }
 
+    /// Copy the contents of the `DivBufMut` into a new, independent
+    /// `DivBufShared`.
+    ///
+    /// This does not count as another writer of the original buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let dbm = dbs.try_mut().unwrap();
+    /// let copy = dbm.clone_to_shared();
+    /// assert_eq!(copy.try_mut().unwrap(), [1, 2, 3][..]);
+    /// ```
+    pub fn clone_to_shared(&self) -> DivBufShared {
+        DivBufShared::from(self.as_ref())
+    }
+
     /// Extend self from iterator, without checking for validity
-    fn extend_unchecked<'a, T>(&mut self, iter: T)
+    fn extend_unchecked<T>(&mut self, iter: T)
     where
-        T: IntoIterator<Item = &'a u8>,
+        T: IntoIterator,
+        T::Item: Borrow<u8>,
+    {
+        /// Recomputes a terminal `DivBufMut`'s length when dropped, so it will
+        /// include any bytes that were added before the iterator panicked.
+        struct Resync<'a>(&'a mut DivBufMut);
+
+        impl<'a> Drop for Resync<'a> {
+            fn drop(&mut self) {
+                let len = unsafe { &(*self.0.inner).len };
+                self.0.len = len.load(Relaxed) - self.0.begin;
+            }
+        }
+
+        let resync = Resync(self);
+        resync.0.modify_vec(|vec| {
+            vec.extend(iter.into_iter().map(|b| *b.borrow()))
+        });
+    }
+
+    /// Overwrite the `DivBufMut`'s contents with bytes from an iterator.
+    ///
+    /// Writing stops at the end of the `DivBufMut` or of the iterator,
+    /// whichever comes first.  Returns the number of bytes written.  Unlike
+    /// [`extend`], this never changes the buffer's length, so it works on
+    /// non-terminal `DivBufMut`s too.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0; 6]);
+    /// let mut dbm0 = dbs.try_mut().unwrap();
+    /// let _dbm1 = dbm0.split_off(4);
+    /// assert_eq!(dbm0.fill_from_iter(1..), 4);
+    /// assert_eq!(dbm0, [1, 2, 3, 4][..]);
+    /// ```
+    ///
+    /// [`extend`]: #method.extend
+    pub fn fill_from_iter<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = u8>,
     {
-        let inner = unsafe { &mut *self.inner };
-        let oldlen = inner.vec.len();
-        inner.vec.extend(iter);
-        self.len += inner.vec.len() - oldlen;
+        let mut n = 0;
+        for (dst, src) in self.iter_mut().zip(iter) {
+            *dst = src;
+            n += 1;
+        }
+        n
     }
 
     /// Downgrade this `DivBufMut` into a read-only `DivBuf`
@@ -821,7 +2990,7 @@ impl DivBufMut {
         // other DivButMuts that overlap with this one, so it's safe to create a
         // DivBuf whose range is restricted to what self covers
         let inner = unsafe { &*self.inner };
-        let old_accessors = inner.accessors.fetch_add(1, Relaxed);
+        let old_accessors = add_reader(&inner.accessors, Relaxed);
         debug_assert!(old_accessors >> WRITER_SHIFT > 0);
         DivBuf {
             inner: self.inner,
@@ -844,52 +3013,345 @@ impl DivBufMut {
     /// # Examples
     /// ```
     /// # use divbuf::*;
-    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5, 6, 7]);
-    /// let dbm = dbs.try_mut().unwrap();
-    /// let mut iter = dbm.into_chunks(3);
-    /// assert_eq!(&iter.next().unwrap()[..], &[0, 1, 2][..]);
-    /// assert_eq!(&iter.next().unwrap()[..], &[3, 4, 5][..]);
-    /// assert_eq!(&iter.next().unwrap()[..], &[6, 7][..]);
-    /// assert!(&iter.next().is_none())
+    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5, 6, 7]);
+    /// let dbm = dbs.try_mut().unwrap();
+    /// let mut iter = dbm.into_chunks(3);
+    /// assert_eq!(&iter.next().unwrap()[..], &[0, 1, 2][..]);
+    /// assert_eq!(&iter.next().unwrap()[..], &[3, 4, 5][..]);
+    /// assert_eq!(&iter.next().unwrap()[..], &[6, 7][..]);
+    /// assert!(&iter.next().is_none())
+    /// ```
+    pub fn into_chunks(self, size: usize) -> ChunksMut {
+        assert!(size != 0);
+        ChunksMut::new(self, size)
+    }
+
+    /// Break the buffer up into equal sized chunks, holding back any
+    /// remainder.
+    ///
+    /// Like [`into_chunks`], but every chunk yielded will be exactly `size`
+    /// bytes long.  If the `DivBufMut` is not evenly divisible by `size`, then
+    /// the leftover bytes can be retrieved from the iterator's
+    /// [`remainder`](struct.ChunksExactMut.html#method.remainder) method.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5, 6, 7]);
+    /// let dbm = dbs.try_mut().unwrap();
+    /// let mut iter = dbm.into_chunks_exact(3);
+    /// assert_eq!(&iter.next().unwrap()[..], &[0, 1, 2][..]);
+    /// assert_eq!(&iter.next().unwrap()[..], &[3, 4, 5][..]);
+    /// assert!(&iter.next().is_none());
+    /// assert_eq!(&iter.remainder()[..], &[6, 7][..]);
+    /// ```
+    ///
+    /// [`into_chunks`]: #method.into_chunks
+    pub fn into_chunks_exact(self, size: usize) -> ChunksExactMut {
+        assert!(size != 0);
+        ChunksExactMut::new(self, size)
+    }
+
+    /// Returns true if every byte in the `DivBufMut` equals `value`.
+    ///
+    /// This is vacuously true for an empty `DivBufMut`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0, 0, 0, 1]);
+    /// let mut dbm = dbs.try_mut().unwrap();
+    /// assert!(!dbm.is_all(0));
+    /// let _ = dbm.split_off(3);
+    /// assert!(dbm.is_all(0));
+    /// ```
+    pub fn is_all(&self, value: u8) -> bool {
+        is_all(self, value)
+    }
+
+    /// Returns true if the `DivBufMut` has length 0
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if the `DivBufMut` extends to the end of the `DivBufShared`
+    fn is_terminal(&self) -> bool {
+        let len = unsafe { &(*self.inner).len };
+        self.begin + self.len == len.load(Relaxed)
+    }
+
+    /// Returns an iterator that allows modifying each byte of the `DivBufMut`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let mut dbm = dbs.try_mut().unwrap();
+    /// for x in dbm.iter_mut() {
+    ///     *x *= 2;
+    /// }
+    /// assert_eq!(dbm, [2, 4, 6][..]);
+    /// ```
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, u8> {
+        ops::DerefMut::deref_mut(self).iter_mut()
+    }
+
+    /// Get a pointer to the first byte of this `DivBufMut`.
+    ///
+    /// Only the `Vec` header gets borrowed, never its contents, so that slices
+    /// of sibling buffers don't invalidate each other under Stacked Borrows.
+    /// The pointer is valid for writes, because it comes from
+    /// `Vec::as_mut_ptr`.
+    fn window_ptr(&self) -> *mut u8 {
+        let vec = unsafe { &(*self.inner).vec };
+        assert!(self.begin + self.len <= vec.len());
+        unsafe { (*self.inner).ptr.add(self.begin) }
+    }
+
+    /// Get the length of this `DivBufMut`, _not_ the underlying storage
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Replaces each byte of the `DivBufMut` with the result of applying `f`
+    /// to it.
+    ///
+    /// Unlike the `try_*` methods, this works on non-terminal buffers too,
+    /// since it never changes the length.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let mut dbm = dbs.try_mut().unwrap();
+    /// dbm.map_bytes(|x| x ^ 0xff);
+    /// assert_eq!(dbm, [0xfe, 0xfd, 0xfc][..]);
+    /// ```
+    pub fn map_bytes<F: FnMut(u8) -> u8>(&mut self, mut f: F) {
+        for x in self.iter_mut() {
+            *x = f(*x);
+        }
+    }
+
+    /// Fill the `DivBufMut` with bytes read from `r`.
+    ///
+    /// Reads until either the `DivBufMut` is full or `r` reaches end-of-file,
+    /// and returns the number of bytes read.  The existing contents are
+    /// overwritten; the `DivBufMut` is never extended, so it need not be
+    /// terminal.  Interrupted reads are retried.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0; 6]);
+    /// let mut dbm = dbs.try_mut().unwrap();
+    /// let mut cursor = std::io::Cursor::new(b"abcd");
+    /// assert_eq!(dbm.read_from(&mut cursor).unwrap(), 4);
+    /// assert_eq!(dbm, b"abcd\0\0"[..]);
+    /// ```
+    pub fn read_from<R: io::Read>(&mut self, r: &mut R) -> io::Result<usize> {
+        let mut total = 0;
+        while total < self.len {
+            match r.read(&mut self[total..]) {
+                Ok(0) => break,
+                Ok(n) => total += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(total)
+    }
+
+    /// Returns the number of bytes that can be appended to this `DivBufMut`
+    /// without reallocating.
+    ///
+    /// Only a terminal `DivBufMut` can use the buffer's spare capacity, so for
+    /// any other this returns 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::with_capacity(64);
+    /// let mut dbm = dbs.try_mut().unwrap();
+    /// dbm.extend(&[1, 2, 3, 4]);
+    /// assert_eq!(dbm.remaining_capacity(), dbs.capacity() - 4);
+    /// let dbm1 = dbm.split_to(2);
+    /// assert_eq!(dbm1.remaining_capacity(), 0);
+    /// ```
+    pub fn remaining_capacity(&self) -> usize {
+        if self.is_terminal() {
+            let capacity = unsafe { &(*self.inner).capacity };
+            let len = unsafe { &(*self.inner).len };
+            capacity.load(Relaxed) - len.load(Relaxed)
+        } else {
+            0
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more bytes to be inserted
+    /// into the buffer.
+    ///
+    /// Like [`extend`], this method will panic if the `DivBufMut` is
+    /// non-terminal.
+    ///
+    /// [`extend`]: #method.extend
+    pub fn reserve(&mut self, additional: usize) {
+        // panic if this DivBufMut does not extend to the end of the
+        // DivBufShared
+        assert!(
+            self.is_terminal(),
+            "Can't reserve from the middle of a buffer"
+        );
+        self.modify_vec(|vec| vec.reserve(additional))
+    }
+
+    /// Resize this `DivBufMut` in-place.
+    ///
+    /// If `new_len` is greater than the existing length, then the buffer will
+    /// be extended by the difference, with each element filled by `value`.  If
+    /// `new_len` is less than the existing length, then the buffer is simply
+    /// truncated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `DivBufMut` is not terminal.  See [`try_resize`] for a
+    /// non-panicking version.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2]);
+    /// let mut dbm0 = dbs.try_mut().unwrap();
+    /// dbm0.resize(4, 0);
+    /// assert_eq!(dbm0, [1, 2, 0, 0][..]);
+    /// ```
+    ///
+    /// [`try_resize`]: #method.try_resize
+    pub fn resize(&mut self, new_len: usize, value: u8) {
+        // panic if this DivBufMut does not extend to the end of the
+        // DivBufShared
+        assert!(
+            self.is_terminal(),
+            "Can't resize from a non-terminal buffer"
+        );
+        self.resize_unchecked(new_len, value);
+    }
+
+    /// Resize self, without checking for validity
+    fn resize_unchecked(&mut self, new_len: usize, value: u8) {
+        let begin = self.begin;
+        self.modify_vec(|vec| vec.resize(new_len + begin, value));
+        self.len = new_len;
+    }
+
+    /// Run `f` on the underlying `Vec`, then publish its new length and
+    /// capacity.
+    ///
+    /// Every change to the `Vec`'s length or capacity must go through here,
+    /// so that [`DivBufShared::len`] can be called concurrently.  The new
+    /// length and capacity get published even if `f` panics.
+    ///
+    /// [`DivBufShared::len`]: struct.DivBufShared.html#method.len
+    fn modify_vec<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Vec<u8>) -> R,
+    {
+        /// Publishes the `Vec`'s length, capacity, and data pointer when
+        /// dropped
+        struct Publish {
+            inner:        *mut Inner,
+            #[cfg(any(debug_assertions, feature = "testing"))]
+            old_capacity: usize,
+        }
+
+        impl Drop for Publish {
+            fn drop(&mut self) {
+                let vec = unsafe { &mut (*self.inner).vec };
+                let (len, capacity) = (vec.len(), vec.capacity());
+                let ptr = vec.as_mut_ptr();
+                unsafe {
+                    (*self.inner).ptr = ptr;
+                    (*self.inner).len.store(len, Relaxed);
+                    (*self.inner).capacity.store(capacity, Relaxed);
+                }
+                #[cfg(any(debug_assertions, feature = "testing"))]
+                if capacity != self.old_capacity {
+                    let reallocs = unsafe { &(*self.inner).reallocs };
+                    reallocs.fetch_add(1, Relaxed);
+                }
+            }
+        }
+
+        let vec = unsafe { &mut (*self.inner).vec };
+        let _publish = Publish {
+            inner: self.inner,
+            #[cfg(any(debug_assertions, feature = "testing"))]
+            old_capacity: vec.capacity(),
+        };
+        f(vec)
+    }
+
+    /// Forces the length of the `DivBufMut` to `new_len`, without
+    /// initializing any new bytes.
+    ///
+    /// If the `DivBufMut` is terminal, then the length of the underlying
+    /// buffer changes too.  This is the `DivBufMut` equivalent of
+    /// `Vec::set_len`, and is typically paired with [`spare_capacity_mut`].
+    ///
+    /// # Safety
+    ///
+    /// - If the `DivBufMut` is terminal, then `new_len` must not exceed its
+    ///   length plus [`remaining_capacity`], and any newly included bytes
+    ///   must have been initialized.
+    /// - Otherwise, `new_len` must not exceed its current length.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::with_capacity(64);
+    /// let mut dbm = dbs.try_mut().unwrap();
+    /// for (i, b) in dbm.spare_capacity_mut()[..4].iter_mut().enumerate() {
+    ///     *b = std::mem::MaybeUninit::new(i as u8);
+    /// }
+    /// unsafe { dbm.set_len(4) };
+    /// assert_eq!(dbm, [0, 1, 2, 3][..]);
     /// ```
-    pub fn into_chunks(self, size: usize) -> ChunksMut {
-        assert!(size != 0);
-        ChunksMut::new(self, size)
-    }
-
-    /// Returns true if the `DivBufMut` has length 0
-    pub fn is_empty(&self) -> bool {
-        self.len == 0
-    }
-
-    /// Returns true if the `DivBufMut` extends to the end of the `DivBufShared`
-    fn is_terminal(&self) -> bool {
-        let inner = unsafe { &*self.inner };
-        let oldlen = inner.vec.len();
-        self.begin + self.len == oldlen
-    }
-
-    /// Get the length of this `DivBufMut`, _not_ the underlying storage
-    pub fn len(&self) -> usize {
-        self.len
+    ///
+    /// [`remaining_capacity`]: #method.remaining_capacity
+    /// [`spare_capacity_mut`]: #method.spare_capacity_mut
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        if self.is_terminal() {
+            let end = self.begin + new_len;
+            self.modify_vec(|vec| {
+                debug_assert!(end <= vec.capacity());
+                vec.set_len(end)
+            });
+        } else {
+            debug_assert!(new_len <= self.len);
+        }
+        self.len = new_len;
     }
 
-    /// Reserves capacity for at least `additional` more bytes to be inserted
-    /// into the buffer.
+    /// Returns the spare capacity past the end of the `DivBufMut`, as a slice
+    /// of possibly uninitialized bytes.
     ///
-    /// Like [`extend`], this method will panic if the `DivBufMut` is
-    /// non-terminal.
+    /// After writing to it, use [`set_len`] to include those bytes in the
+    /// `DivBufMut`.  Only a terminal `DivBufMut` can use the buffer's spare
+    /// capacity, so for any other this returns an empty slice.
     ///
-    /// [`extend`]: #method.extend
-    pub fn reserve(&mut self, additional: usize) {
-        // panic if this DivBufMut does not extend to the end of the
-        // DivBufShared
-        assert!(
-            self.is_terminal(),
-            "Can't reserve from the middle of a buffer"
-        );
-        let inner = unsafe { &mut *self.inner };
-        inner.vec.reserve(additional)
+    /// [`set_len`]: #method.set_len
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        if !self.is_terminal() {
+            return &mut [];
+        }
+        let vec = unsafe { &(*self.inner).vec };
+        let len = vec.len();
+        let spare = vec.capacity() - len;
+        // As in window_ptr, only the Vec header gets borrowed.
+        unsafe {
+            let p = (*self.inner).ptr.add(len);
+            slice::from_raw_parts_mut(p as *mut MaybeUninit<u8>, spare)
+        }
     }
 
     /// Splits the DivBufMut into two at the given index.
@@ -954,13 +3416,138 @@ impl DivBufMut {
         left_half
     }
 
+    /// Swaps two bytes in the `DivBufMut`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+    /// let mut dbm = dbs.try_mut().unwrap();
+    /// dbm.swap(0, 3);
+    /// assert_eq!(dbm, [4, 2, 3, 1][..]);
+    /// ```
+    pub fn swap(&mut self, a: usize, b: usize) {
+        <[u8]>::swap(self, a, b)
+    }
+
+    /// Removes and returns the first `n` bytes of the DivBufMut.
+    ///
+    /// Afterwards self contains the remaining bytes.  This is the same as
+    /// [`split_to`](#method.split_to).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let mut dbm0 = dbs.try_mut().unwrap();
+    /// let header = dbm0.take(2);
+    /// assert_eq!(header, [1, 2][..]);
+    /// assert_eq!(dbm0, [3, 4, 5, 6][..]);
+    /// ```
+    pub fn take(&mut self, n: usize) -> DivBufMut {
+        self.split_to(n)
+    }
+
+    /// Create a new `DivBufShared` that refers to the same storage as this
+    /// `DivBufMut`.
+    ///
+    /// No data is copied.  The new `DivBufShared` is an additional owner of
+    /// the storage, which will live until all owners and accessors have been
+    /// dropped.  Contrast with [`clone_to_shared`], which copies the data.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs0 = DivBufShared::from(vec![1, 2, 3]);
+    /// let mut dbm = dbs0.try_mut().unwrap();
+    /// dbm[0] = 4;
+    /// let dbs1 = dbm.to_shared();
+    /// drop(dbm);
+    /// drop(dbs0);
+    /// assert_eq!(dbs1.try_const().unwrap(), [4, 2, 3][..]);
+    /// ```
+    ///
+    /// [`clone_to_shared`]: #method.clone_to_shared
+    pub fn to_shared(&self) -> DivBufShared {
+        let inner = unsafe { &*self.inner };
+        let old = inner.sharers.fetch_add(1, Acquire);
+        debug_assert!(old > 0);
+        DivBufShared { inner: self.inner }
+    }
+
+    /// Shortens the buffer, keeping the first `len` bytes and dropping the
+    /// rest.
+    ///
+    /// If `len` is greater than the buffer's current length, this has no
+    /// effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `DivBufMut` is not terminal.  See [`try_truncate`] for a
+    /// non-panicking version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let mut dbm0 = dbs.try_mut().unwrap();
+    /// dbm0.truncate(3);
+    /// assert_eq!(dbm0, [1, 2, 3][..]);
+    /// ```
+    ///
+    /// [`try_truncate`]: #method.try_truncate
+    pub fn truncate(&mut self, len: usize) {
+        // panic if this DivBufMut does not extend to the end of the
+        // DivBufShared
+        assert!(
+            self.is_terminal(),
+            "Can't truncate a non-terminal DivBufMut"
+        );
+        self.truncate_unchecked(len);
+    }
+
+    /// Truncate self, without checking for validity
+    fn truncate_unchecked(&mut self, len: usize) {
+        let begin = self.begin;
+        self.modify_vec(|vec| vec.truncate(begin + len));
+        self.len = cmp::min(self.len, len);
+    }
+
+    /// Attempt to append a copy of `src`'s contents to this `DivBufMut`.
+    ///
+    /// Unlike [`unsplit`], `src` may belong to any buffer, because its
+    /// contents are always copied.  If this `DivBufMut` is not terminal, then
+    /// an error will be returned and the buffer will not be modified.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs0 = DivBufShared::from(vec![1, 2]);
+    /// let dbs1 = DivBufShared::from(vec![3, 4]);
+    /// let mut dbm = dbs0.try_mut().unwrap();
+    /// dbm.try_append(&dbs1.try_const().unwrap()).unwrap();
+    /// assert_eq!(dbm, [1, 2, 3, 4][..]);
+    /// ```
+    ///
+    /// [`unsplit`]: #method.unsplit
+    pub fn try_append(&mut self, src: &DivBuf) -> Result<(), Error> {
+        self.try_extend_from_slice(src)
+    }
+
     /// Attempt to extend this `DivBufMut` with bytes from the provided
     /// iterator.
     ///
-    /// If this `DivBufMut` is not terminal, that is if it does not extend to
-    /// the end of the `DivBufShared`, then this operation will return an error
-    /// and the buffer will not be modified.  The [`extend`] method from the
-    /// `Extend` Trait, by contrast, will panic under the same condition.
+    /// The iterator may yield either owned or borrowed bytes.  If this
+    /// `DivBufMut` is not terminal, that is if it does not extend to the end
+    /// of the `DivBufShared`, then this operation will return an error and the
+    /// buffer will not be modified.  The [`extend`] method from the `Extend`
+    /// Trait, by contrast, will panic under the same condition.
     ///
     /// # Examples
     /// ```
@@ -968,12 +3555,15 @@ impl DivBufMut {
     /// let dbs = DivBufShared::with_capacity(64);
     /// let mut dbm0 = dbs.try_mut().unwrap();
     /// assert!(dbm0.try_extend([1, 2, 3].iter()).is_ok());
+    /// assert!(dbm0.try_extend(vec![4, 5, 6]).is_ok());
+    /// assert_eq!(dbm0, [1, 2, 3, 4, 5, 6][..]);
     /// ```
     ///
     /// [`extend`]: #method.extend
-    pub fn try_extend<'a, T>(&mut self, iter: T) -> Result<(), Error>
+    pub fn try_extend<T>(&mut self, iter: T) -> Result<(), Error>
     where
-        T: IntoIterator<Item = &'a u8>,
+        T: IntoIterator,
+        T::Item: Borrow<u8>,
     {
         if self.is_terminal() {
             self.extend_unchecked(iter);
@@ -983,6 +3573,163 @@ impl DivBufMut {
         }
     }
 
+    /// Attempt to extend this `DivBufMut` by copying a slice.
+    ///
+    /// Like [`try_extend`], but faster because it copies all of `src` at once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2]);
+    /// let mut dbm0 = dbs.try_mut().unwrap();
+    /// dbm0.try_extend_from_slice(&[3, 4]).unwrap();
+    /// assert_eq!(dbm0, [1, 2, 3, 4][..]);
+    /// ```
+    ///
+    /// [`try_extend`]: #method.try_extend
+    pub fn try_extend_from_slice(&mut self, src: &[u8]) -> Result<(), Error> {
+        if self.is_terminal() {
+            self.modify_vec(|vec| vec.extend_from_slice(src));
+            self.len += src.len();
+            Ok(())
+        } else {
+            Err(Error("Can't extend into the middle of a buffer"))
+        }
+    }
+
+    /// Attempt to extend this `DivBufMut` with `count` copies of `value`.
+    ///
+    /// Like [`try_extend`], this will fail if the `DivBufMut` is non-terminal.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2]);
+    /// let mut dbm0 = dbs.try_mut().unwrap();
+    /// dbm0.try_extend_repeat(0, 3).unwrap();
+    /// assert_eq!(dbm0, [1, 2, 0, 0, 0][..]);
+    /// ```
+    ///
+    /// [`try_extend`]: #method.try_extend
+    pub fn try_extend_repeat(
+        &mut self,
+        value: u8,
+        count: usize,
+    ) -> Result<(), Error> {
+        if self.is_terminal() {
+            self.modify_vec(|vec| {
+                let new_len =
+                    vec.len().checked_add(count).expect("capacity overflow");
+                vec.resize(new_len, value)
+            });
+            self.len += count;
+            Ok(())
+        } else {
+            Err(Error("Can't extend into the middle of a buffer"))
+        }
+    }
+
+    /// Downgrade this `DivBufMut` into a read-only `DivBuf`, but only if it
+    /// is not empty.
+    ///
+    /// Like [`freeze`], but an empty `DivBufMut` is handed back unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let mut dbm0 = dbs.try_mut().unwrap();
+    /// let dbm1 = dbm0.split_off(3);
+    /// let db = dbm0.try_freeze_nonempty().unwrap();
+    /// assert_eq!(db, [1, 2, 3][..]);
+    /// assert!(dbm1.try_freeze_nonempty().is_err());
+    /// ```
+    ///
+    /// [`freeze`]: #method.freeze
+    pub fn try_freeze_nonempty(self) -> Result<DivBuf, DivBufMut> {
+        if self.is_empty() {
+            Err(self)
+        } else {
+            Ok(self.freeze())
+        }
+    }
+
+    /// Attempt to break the buffer up into equal sized chunks
+    ///
+    /// Like [`into_chunks`], but returns an error along with the original
+    /// `DivBufMut`, rather than panicking, if `size` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5]);
+    /// let dbm = dbs.try_mut().unwrap();
+    /// let (dbm, _e) = dbm.try_into_chunks(0).unwrap_err();
+    /// let mut iter = dbm.try_into_chunks(4).unwrap();
+    /// assert_eq!(&iter.next().unwrap()[..], &[0, 1, 2, 3][..]);
+    /// ```
+    ///
+    /// [`into_chunks`]: #method.into_chunks
+    pub fn try_into_chunks(
+        self,
+        size: usize,
+    ) -> Result<ChunksMut, (DivBufMut, Error)> {
+        if size == 0 {
+            Err((self, Error("Chunk size must be non-zero")))
+        } else {
+            Ok(self.into_chunks(size))
+        }
+    }
+
+    /// Attempt to remove a range of bytes from this `DivBufMut`.
+    ///
+    /// The bytes following `range` will be shifted left to fill the gap, and
+    /// both this `DivBufMut` and the underlying `DivBufShared` will shrink
+    /// accordingly.  `range` is relative to the start of this `DivBufMut`.
+    ///
+    /// If this `DivBufMut` is not terminal, that is if it does not extend to
+    /// the end of the `DivBufShared`, or if `range` is out of bounds, then
+    /// this operation will return an error and the buffer will not be
+    /// modified.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let mut dbm0 = dbs.try_mut().unwrap();
+    /// assert!(dbm0.try_remove_range(1..3).is_ok());
+    /// assert_eq!(dbm0, [1, 4, 5, 6][..]);
+    /// ```
+    pub fn try_remove_range<R>(&mut self, range: R) -> Result<(), Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        if !self.is_terminal() {
+            return Err(Error("Can't remove from a non-terminal buffer"));
+        }
+        let begin = match range.start_bound() {
+            Bound::Included(&b) => Some(b),
+            Bound::Excluded(&b) => b.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e.checked_add(1),
+            Bound::Excluded(&e) => Some(e),
+            Bound::Unbounded => Some(self.len),
+        };
+        match (begin, end) {
+            (Some(b), Some(e)) if b <= e && e <= self.len => {
+                let begin = self.begin;
+                self.modify_vec(|vec| {
+                    vec.drain((begin + b)..(begin + e));
+                });
+                self.len -= e - b;
+                Ok(())
+            }
+            _ => Err(Error("Range out of bounds")),
+        }
+    }
+
     /// Attempt to resize this `DivBufMut` in-place.
     ///
     /// If `new_len` is greater than the existing length, then the buffer will
@@ -1008,15 +3755,48 @@ impl DivBufMut {
         value: u8,
     ) -> Result<(), Error> {
         if self.is_terminal() {
-            let inner = unsafe { &mut *self.inner };
-            inner.vec.resize(new_len + self.begin, value);
-            self.len = new_len;
+            self.resize_unchecked(new_len, value);
             Ok(())
         } else {
             Err(Error("Can't resize from a non-terminal buffer"))
         }
     }
 
+    /// Retains only the bytes specified by the predicate, shifting the kept
+    /// bytes to the front of the buffer and shrinking it.
+    ///
+    /// Like [`try_extend`], will fail if this `DivBufMut` is non-terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(&b"one\ntwo\n"[..]);
+    /// let mut dbm0 = dbs.try_mut().unwrap();
+    /// dbm0.try_retain(|b| b != b'\n').unwrap();
+    /// assert_eq!(dbm0, b"onetwo"[..]);
+    /// ```
+    ///
+    /// [`try_extend`]: #method.try_extend
+    pub fn try_retain<F>(&mut self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(u8) -> bool,
+    {
+        if !self.is_terminal() {
+            return Err(Error("Can't retain from a non-terminal DivBufMut"));
+        }
+        let window: &mut [u8] = self;
+        let mut kept = 0;
+        for i in 0..window.len() {
+            let b = window[i];
+            if f(b) {
+                window[kept] = b;
+                kept += 1;
+            }
+        }
+        self.try_truncate(kept)
+    }
+
     /// Shortens the buffer, keeping the first `len` bytes and dropping the
     /// rest.
     ///
@@ -1038,9 +3818,7 @@ impl DivBufMut {
     /// [`try_extend`]: #method.try_extend
     pub fn try_truncate(&mut self, len: usize) -> Result<(), Error> {
         if self.is_terminal() {
-            let inner = unsafe { &mut *self.inner };
-            inner.vec.truncate(self.begin + len);
-            self.len = cmp::min(self.len, len);
+            self.truncate_unchecked(len);
             Ok(())
         } else {
             Err(Error("Can't truncate a non-terminal DivBufMut"))
@@ -1070,28 +3848,143 @@ impl DivBufMut {
             Ok(())
         }
     }
+
+    /// Overwrite `N` bytes starting at `offset` with the contents of `data`.
+    ///
+    /// The `DivBufMut` never grows, so this will fail without writing anything
+    /// if `offset + N` is past the end of the buffer.  Unlike the `try_*`
+    /// methods, this works on non-terminal `DivBufMut`s.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0; 6]);
+    /// let mut dbm = dbs.try_mut().unwrap();
+    /// dbm.write_array(2, 0x0102_0304u32.to_be_bytes()).unwrap();
+    /// assert_eq!(dbm, [0, 0, 1, 2, 3, 4][..]);
+    /// assert!(dbm.write_array(3, [0; 4]).is_err());
+    /// ```
+    pub fn write_array<const N: usize>(
+        &mut self,
+        offset: usize,
+        data: [u8; N],
+    ) -> Result<(), Error> {
+        match offset.checked_add(N) {
+            Some(end) if end <= self.len => {
+                self[offset..end].copy_from_slice(&data);
+                Ok(())
+            }
+            _ => Err(Error("Range out of bounds")),
+        }
+    }
+
+    /// Overwrite bytes starting at `offset` with the contents of `src`.
+    ///
+    /// The copy is clamped to the end of the `DivBufMut`, which never grows.
+    /// Returns the number of bytes written, or an error if `offset` is past
+    /// the end of the buffer.  Unlike the `io::Write` implementation, this
+    /// works on non-terminal `DivBufMut`s.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![0; 6]);
+    /// let mut dbm0 = dbs.try_mut().unwrap();
+    /// let _dbm1 = dbm0.split_off(4);
+    /// assert_eq!(dbm0.write_at(2, &[1, 2, 3]).unwrap(), 2);
+    /// assert_eq!(dbm0, [0, 0, 1, 2][..]);
+    /// ```
+    pub fn write_at(
+        &mut self,
+        offset: usize,
+        src: &[u8],
+    ) -> Result<usize, Error> {
+        if offset > self.len {
+            return Err(Error("Offset out of bounds"));
+        }
+        let n = cmp::min(src.len(), self.len - offset);
+        self[offset..offset + n].copy_from_slice(&src[..n]);
+        Ok(n)
+    }
+
+    /// Append a frame consisting of a 4-byte big-endian length prefix followed
+    /// by `body`.
+    ///
+    /// Like [`try_extend`], will fail if this `DivBufMut` is non-terminal.  It
+    /// will also fail if `body` is too long for its length to fit in a `u32`.
+    /// On failure, nothing will be written.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::with_capacity(64);
+    /// let mut dbm = dbs.try_mut().unwrap();
+    /// dbm.write_frame_u32_be(&[9, 8]).unwrap();
+    /// assert_eq!(dbm, [0, 0, 0, 2, 9, 8][..]);
+    /// ```
+    ///
+    /// [`try_extend`]: #method.try_extend
+    pub fn write_frame_u32_be(&mut self, body: &[u8]) -> Result<(), Error> {
+        let body_len = u32::try_from(body.len())
+            .map_err(|_| Error("Frame body is too long"))?;
+        if self.is_terminal() {
+            self.extend_unchecked(body_len.to_be_bytes().iter().chain(body));
+            Ok(())
+        } else {
+            Err(Error("Can't extend into the middle of a buffer"))
+        }
+    }
+
+    /// Overwrite every byte of the `DivBufMut` with zero, in a way that the
+    /// optimizer won't elide.
+    ///
+    /// Only this window is wiped, so this works on non-terminal `DivBufMut`s
+    /// too.  The length is unchanged.  To wipe the whole buffer when it's
+    /// freed, use [`DivBufShared::new_zeroizing`] instead.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(b"user:hunter2".to_vec());
+    /// let mut dbm = dbs.try_mut().unwrap();
+    /// let mut password = dbm.split_off(5);
+    /// password.zeroize();
+    /// assert_eq!(password, [0; 7][..]);
+    /// assert_eq!(dbm, b"user:"[..]);
+    /// ```
+    ///
+    /// [`DivBufShared::new_zeroizing`]: struct.DivBufShared.html#method.new_zeroizing
+    #[cfg(feature = "zeroize")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+    pub fn zeroize(&mut self) {
+        ops::DerefMut::deref_mut(self).zeroize();
+    }
 }
 
 impl AsRef<[u8]> for DivBufMut {
     fn as_ref(&self) -> &[u8] {
-        unsafe {
-            let inner = &*self.inner;
-            &inner.vec[self.begin..(self.begin + self.len)][..]
-        }
+        unsafe { slice::from_raw_parts(self.window_ptr(), self.len) }
     }
 }
 
 impl Borrow<[u8]> for DivBufMut {
     fn borrow(&self) -> &[u8] {
-        let inner = unsafe { &*self.inner };
-        &inner.vec[..]
+        self.as_ref()
     }
 }
 
 impl BorrowMut<[u8]> for DivBufMut {
     fn borrow_mut(&mut self) -> &mut [u8] {
-        let inner = unsafe { &mut *self.inner };
-        &mut inner.vec[..]
+        ops::DerefMut::deref_mut(self)
+    }
+}
+
+impl Debug for DivBufMut {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("DivBufMut")
+            .field("len", &self.len)
+            .field("data", &DebugBytes(self))
+            .finish()
     }
 }
 
@@ -1099,33 +3992,19 @@ impl ops::Deref for DivBufMut {
     type Target = [u8];
 
     fn deref(&self) -> &[u8] {
-        unsafe {
-            let inner = &*self.inner;
-            &inner.vec[self.begin..(self.begin + self.len)][..]
-        }
+        unsafe { slice::from_raw_parts(self.window_ptr(), self.len) }
     }
 }
 
 impl ops::DerefMut for DivBufMut {
     fn deref_mut(&mut self) -> &mut [u8] {
-        unsafe {
-            let inner = &mut *self.inner;
-            &mut inner.vec[self.begin..(self.begin + self.len)][..]
-        }
+        unsafe { slice::from_raw_parts_mut(self.window_ptr(), self.len) }
     }
 }
 
 impl Drop for DivBufMut {
     fn drop(&mut self) {
-        let inner = unsafe { &*self.inner };
-        if inner.accessors.fetch_sub(ONE_WRITER, Release) == ONE_WRITER
-            && inner.sharers.load(Relaxed) == 0
-        {
-            atomic::fence(Acquire);
-            unsafe {
-                drop(Box::from_raw(self.inner));
-            }
-        }
+        unsafe { release_writer(self.inner) }
     }
 }
 
@@ -1144,6 +4023,21 @@ impl<'a> Extend<&'a u8> for DivBufMut {
     }
 }
 
+impl Extend<u8> for DivBufMut {
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = u8>,
+    {
+        // panic if this DivBufMut does not extend to the end of the
+        // DivBufShared
+        assert!(
+            self.is_terminal(),
+            "Can't extend into the middle of a buffer"
+        );
+        self.extend_unchecked(iter);
+    }
+}
+
 impl hash::Hash for DivBufMut {
     fn hash<H>(&self, state: &mut H)
     where
@@ -1174,6 +4068,18 @@ impl PartialEq<[u8]> for DivBufMut {
     }
 }
 
+impl<const N: usize> PartialEq<[u8; N]> for DivBufMut {
+    fn eq(&self, other: &[u8; N]) -> bool {
+        self.as_ref() == &other[..]
+    }
+}
+
+impl<const N: usize> PartialEq<DivBufMut> for [u8; N] {
+    fn eq(&self, other: &DivBufMut) -> bool {
+        &self[..] == other.as_ref()
+    }
+}
+
 impl PartialOrd for DivBufMut {
     fn partial_cmp(&self, other: &DivBufMut) -> Option<cmp::Ordering> {
         Some(self.cmp(other))
@@ -1193,25 +4099,69 @@ unsafe impl Sync for DivBufMut {}
 
 impl io::Write for DivBufMut {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.try_extend(buf)
+        self.try_extend_from_slice(buf)
             .map(|_| buf.len())
             .map_err(|s| io::Error::new(io::ErrorKind::Other, s))
     }
 
     fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
-        self.try_extend(buf)
+        self.try_extend_from_slice(buf)
             .map_err(|s| io::Error::new(io::ErrorKind::Other, s))
     }
 
+    // is_write_vectored can't be overridden until the can_vector feature
+    // stabilizes.
+    // <https://github.com/rust-lang/rust/issues/69941>
+    fn write_vectored(&mut self, bufs: &[io::IoSlice]) -> io::Result<usize> {
+        if !self.is_terminal() {
+            let e = Error("Can't extend into the middle of a buffer");
+            return Err(io::Error::new(io::ErrorKind::Other, e));
+        }
+        let total = bufs.iter().map(|b| b.len()).sum();
+        self.modify_vec(|vec| {
+            vec.reserve(total);
+            for buf in bufs {
+                vec.extend_from_slice(buf);
+            }
+        });
+        self.len += total;
+        Ok(total)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
 }
 
 impl DivBufInaccessible {
+    /// Returns true if this window still lies within the buffer.
+    ///
+    /// A `DivBufInaccessible` doesn't prevent the buffer from being truncated.
+    /// If that happens, then it can no longer be upgraded.  Note that unless
+    /// the caller holds a [`DivBuf`] for the same buffer, the result may be
+    /// stale by the time it is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let dbi = dbs.try_const().unwrap().slice_from(4).clone_inaccessible();
+    /// assert!(dbi.is_valid());
+    /// dbs.try_mut().unwrap().try_truncate(2).unwrap();
+    /// assert!(!dbi.is_valid());
+    /// assert!(dbi.try_const().is_err());
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    pub fn is_valid(&self) -> bool {
+        let len = unsafe { &(*self.inner).len };
+        self.begin + self.len <= len.load(Relaxed)
+    }
+
     /// Try to upgrade to a [`DivBuf`].
     ///
-    /// Will fail if there are any [`DivBufMut`]s referring to this same buffer.
+    /// Will fail if there are any [`DivBufMut`]s referring to this same buffer,
+    /// or if the buffer has been truncated (see [`is_valid`]).
     ///
     /// # Examples
     /// ```
@@ -1222,23 +4172,32 @@ impl DivBufInaccessible {
     /// drop(dbm);
     /// let _db: DivBuf = dbi.try_const().unwrap();
     /// ```
+    ///
+    /// [`is_valid`]: #method.is_valid
     pub fn try_const(&self) -> Result<DivBuf, Error> {
         let inner = unsafe { &*self.inner };
-        if inner.accessors.fetch_add(1, Acquire) >> WRITER_SHIFT != 0 {
-            inner.accessors.fetch_sub(1, Relaxed);
-            Err(Error("Cannot create a DivBuf when DivBufMuts are active"))
-        } else {
-            Ok(DivBuf {
+        if inner.try_acquire_reader() {
+            let db = DivBuf {
                 inner: self.inner,
                 begin: self.begin,
                 len:   self.len,
-            })
+            };
+            // Now that we hold the reader lock, nobody else can truncate the
+            // buffer.
+            if self.is_valid() {
+                Ok(db)
+            } else {
+                Err(Error("The buffer was truncated"))
+            }
+        } else {
+            Err(Error("Cannot create a DivBuf when DivBufMuts are active"))
         }
     }
 
     /// Try to upgrade to a [`DivBufMut`].
     ///
-    /// Will fail if there are any [`DivBufMut`]s referring to this same buffer.
+    /// Will fail if there are any [`DivBufMut`]s referring to this same buffer,
+    /// or if the buffer has been truncated (see [`is_valid`]).
     ///
     /// # Examples
     /// ```
@@ -1249,22 +4208,60 @@ impl DivBufInaccessible {
     /// drop(dbm);
     /// let _dbm: DivBufMut = dbi.try_mut().unwrap();
     /// ```
+    ///
+    /// [`is_valid`]: #method.is_valid
     pub fn try_mut(&self) -> Result<DivBufMut, Error> {
         let inner = unsafe { &*self.inner };
-        if inner
-            .accessors
-            .compare_exchange(0, ONE_WRITER, AcqRel, Acquire)
-            .is_ok()
-        {
-            Ok(DivBufMut {
+        if inner.try_acquire_writer() {
+            let dbm = DivBufMut {
                 inner: self.inner,
                 begin: self.begin,
                 len:   self.len,
-            })
+            };
+            // Now that we hold the writer lock, nobody else can truncate the
+            // buffer.
+            if self.is_valid() {
+                Ok(dbm)
+            } else {
+                Err(Error("The buffer was truncated"))
+            }
         } else {
             Err(Error("Cannot upgrade when DivBufMuts are active"))
         }
     }
+
+    /// Try to upgrade to a [`DivBufMut`] that can be extended.
+    ///
+    /// Like [`try_mut`], this will fail if there are any other [`DivBuf`]s or
+    /// `DivBufMut`s referring to this same buffer.  It will also fail if this
+    /// window doesn't extend to the end of the buffer, for example because the
+    /// buffer was extended after the `DivBufInaccessible` was created.  Only a
+    /// terminal `DivBufMut` can be extended.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::with_capacity(4096);
+    /// let dbi = dbs.try_mut().unwrap().clone_inaccessible();
+    /// let mut dbm = dbi.try_mut_terminal().unwrap();
+    /// dbm.try_extend(b"log entry").unwrap();
+    /// drop(dbm);
+    /// assert_eq!(dbs.try_const().unwrap(), b"log entry"[..]);
+    /// ```
+    ///
+    /// [`DivBuf`]: struct.DivBuf.html
+    /// [`DivBufMut`]: struct.DivBufMut.html
+    /// [`try_mut`]: #method.try_mut
+    pub fn try_mut_terminal(&self) -> Result<DivBufMut, Error> {
+        let dbm = self.try_mut()?;
+        // Now that we hold the writer lock, nobody else can change the
+        // buffer's length.
+        if dbm.is_terminal() {
+            Ok(dbm)
+        } else {
+            Err(Error("Can't extend into the middle of a buffer"))
+        }
+    }
 }
 
 impl Clone for DivBufInaccessible {
@@ -1280,19 +4277,40 @@ impl Clone for DivBufInaccessible {
     }
 }
 
+impl Debug for DivBufInaccessible {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        // The data can't be safely read without a DivBuf
+        f.debug_struct("DivBufInaccessible")
+            .field("offset", &self.begin)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
 impl Drop for DivBufInaccessible {
     fn drop(&mut self) {
-        let inner = unsafe { &*self.inner };
-        if inner.sharers.fetch_sub(1, Release) == 1
-            && inner.accessors.load(Relaxed) == 0
-        {
-            // See the comments in std::sync::Arc::drop for why the fence is
-            // required.
-            atomic::fence(Acquire);
-            unsafe {
-                drop(Box::from_raw(self.inner));
-            }
-        }
+        unsafe { release_sharer(self.inner) }
+    }
+}
+
+impl Eq for DivBufInaccessible {}
+
+impl hash::Hash for DivBufInaccessible {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        (self.inner as usize).hash(state);
+        self.begin.hash(state);
+        self.len.hash(state);
+    }
+}
+
+impl PartialEq for DivBufInaccessible {
+    fn eq(&self, other: &DivBufInaccessible) -> bool {
+        self.inner == other.inner
+            && self.begin == other.begin
+            && self.len == other.len
     }
 }
 
@@ -1302,3 +4320,360 @@ impl Drop for DivBufInaccessible {
 // is Sync, which it is).  Therefore, DivBufInaccessible is both Send and Sync.
 unsafe impl Send for DivBufInaccessible {}
 unsafe impl Sync for DivBufInaccessible {}
+
+impl DivBufCursor {
+    /// Create a new cursor, positioned at the start of `db`.
+    pub fn new(db: DivBuf) -> Self {
+        DivBufCursor { db, pos: 0 }
+    }
+
+    /// Get a reference to the underlying `DivBuf`
+    pub fn get_ref(&self) -> &DivBuf {
+        &self.db
+    }
+
+    /// Consume the cursor, returning the underlying `DivBuf`
+    pub fn into_inner(self) -> DivBuf {
+        self.db
+    }
+
+    /// Return the next byte without advancing the cursor, or `None` if the
+    /// cursor is at the end of the buffer.
+    pub fn peek(&self) -> Option<u8> {
+        self.db.get(self.pos).cloned()
+    }
+
+    /// Returns the current position of the cursor, relative to the start of
+    /// the buffer.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Read the next byte and advance the cursor, or return `None` if the
+    /// cursor is at the end of the buffer.
+    pub fn read_u8(&mut self) -> Option<u8> {
+        let r = self.peek();
+        if r.is_some() {
+            self.pos += 1;
+        }
+        r
+    }
+
+    /// Returns the number of bytes between the cursor and the end of the
+    /// buffer.
+    pub fn remaining(&self) -> usize {
+        self.db.len().saturating_sub(self.pos)
+    }
+
+    /// Move the cursor back to the start of the buffer.
+    pub fn rewind(&mut self) {
+        self.pos = 0;
+    }
+
+    /// Move the cursor by `offset` bytes, relative to its current position.
+    ///
+    /// Returns the new position.  Will fail, leaving the cursor unmoved, if
+    /// the new position would lie before the start or after the end of the
+    /// buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// let dbs = DivBufShared::from(vec![1, 2, 3]);
+    /// let mut cursor = DivBufCursor::new(dbs.try_const().unwrap());
+    /// assert_eq!(cursor.seek(2).unwrap(), 2);
+    /// assert_eq!(cursor.seek(-1).unwrap(), 1);
+    /// assert_eq!(cursor.read_u8(), Some(2));
+    /// ```
+    pub fn seek(&mut self, offset: isize) -> Result<usize, Error> {
+        let newpos = if offset >= 0 {
+            self.pos.checked_add(offset as usize)
+        } else {
+            self.pos.checked_sub(offset.wrapping_neg() as usize)
+        };
+        match newpos {
+            Some(p) if p <= self.db.len() => {
+                self.pos = p;
+                Ok(p)
+            }
+            _ => Err(Error("Can't seek outside of the buffer")),
+        }
+    }
+
+    /// Set the position of the cursor, relative to the start of the buffer.
+    ///
+    /// It is legal to set the position past the end of the buffer.
+    /// Subsequent reads will simply return `None`.
+    pub fn set_position(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+}
+
+impl DivBufReader {
+    /// Get a reference to the underlying `DivBuf`
+    pub fn get_ref(&self) -> &DivBuf {
+        &self.db
+    }
+
+    /// Consume the reader, returning the underlying `DivBuf`
+    pub fn into_inner(self) -> DivBuf {
+        self.db
+    }
+
+    /// Returns the current position of the reader, relative to the start of
+    /// the buffer.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+impl io::Read for DivBufReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = &self.db[self.pos..];
+        let n = cmp::min(buf.len(), remaining.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl io::Seek for DivBufReader {
+    /// Move the reader's position.
+    ///
+    /// Unlike `std::io::Cursor`, seeking before the start or past the end of
+    /// the buffer is not an error.  Instead, the new position will be clamped
+    /// to the buffer's bounds.
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        let len = self.db.len() as i128;
+        let newpos = match pos {
+            io::SeekFrom::Start(n) => i128::from(n),
+            io::SeekFrom::End(n) => len + i128::from(n),
+            io::SeekFrom::Current(n) => self.pos as i128 + i128::from(n),
+        };
+        self.pos = cmp::max(0, cmp::min(newpos, len)) as usize;
+        Ok(self.pos as u64)
+    }
+}
+
+impl DivBufBuilder {
+    /// Returns the number of bytes the builder can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// Consume the builder, returning a `DivBufShared` of everything pushed
+    /// so far.
+    pub fn finish(self) -> DivBufShared {
+        DivBufShared::from(self.vec)
+    }
+
+    /// Returns true if nothing has been pushed yet.
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Returns the number of bytes pushed so far.
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a copy of `src` to the builder.
+    pub fn push_slice(&mut self, src: &[u8]) {
+        self.vec.extend_from_slice(src);
+    }
+
+    /// Create a new, empty builder with room for at least `capacity` bytes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        DivBufBuilder {
+            vec: Vec::with_capacity(capacity),
+        }
+    }
+}
+
+impl HashedDivBuf {
+    /// Hash `db`'s contents, and store the result alongside it.
+    pub fn new(db: DivBuf) -> Self {
+        let mut hasher = DefaultHasher::new();
+        hash::Hash::hash(&db, &mut hasher);
+        let hash = hasher.finish();
+        HashedDivBuf { db, hash }
+    }
+
+    /// Returns the precomputed hash.
+    pub fn cached_hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Get a reference to the underlying `DivBuf`
+    pub fn get_ref(&self) -> &DivBuf {
+        &self.db
+    }
+
+    /// Consume the `HashedDivBuf`, returning the underlying `DivBuf`
+    pub fn into_inner(self) -> DivBuf {
+        self.db
+    }
+}
+
+impl AsRef<[u8]> for HashedDivBuf {
+    fn as_ref(&self) -> &[u8] {
+        self.db.as_ref()
+    }
+}
+
+impl Eq for HashedDivBuf {}
+
+impl From<DivBuf> for HashedDivBuf {
+    fn from(db: DivBuf) -> Self {
+        HashedDivBuf::new(db)
+    }
+}
+
+impl hash::Hash for HashedDivBuf {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        state.write_u64(self.hash);
+    }
+}
+
+impl PartialEq for HashedDivBuf {
+    fn eq(&self, other: &HashedDivBuf) -> bool {
+        self.hash == other.hash && self.db == other.db
+    }
+}
+
+impl DivBufPool {
+    /// Returns the capacity of the buffers issued by this pool.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity
+    }
+
+    /// Get an empty buffer with at least the pool's capacity, reusing
+    /// recycled storage if any is available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use divbuf::*;
+    /// let pool = DivBufPool::new(64);
+    /// let dbs = pool.get();
+    /// let ptr = dbs.try_const().unwrap().as_ptr();
+    /// drop(dbs);
+    /// let dbs = pool.get();
+    /// assert_eq!(ptr, dbs.try_const().unwrap().as_ptr());
+    /// ```
+    pub fn get(&self) -> DivBufShared {
+        let recycled = self.inner.free.lock().ok().and_then(|mut f| f.pop());
+        let v =
+            recycled.unwrap_or_else(|| Vec::with_capacity(self.inner.capacity));
+        let dbs = DivBufShared::from(v);
+        // Nobody else can have access to the Inner yet.
+        unsafe { (*dbs.inner).pool = Some(Arc::downgrade(&self.inner)) };
+        dbs
+    }
+
+    /// Returns the number of recycled buffers waiting to be reused.
+    pub fn idle(&self) -> usize {
+        self.inner.free.lock().map(|f| f.len()).unwrap_or(0)
+    }
+
+    /// Create a new, empty pool whose buffers will each have room for at least
+    /// `capacity` bytes.
+    pub fn new(capacity: usize) -> Self {
+        let inner = Arc::new(PoolInner {
+            capacity,
+            free: Mutex::new(Vec::new()),
+        });
+        DivBufPool { inner }
+    }
+}
+
+impl Debug for DivBufPool {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("DivBufPool")
+            .field("capacity", &self.capacity())
+            .field("idle", &self.idle())
+            .finish()
+    }
+}
+
+impl PoolInner {
+    /// Return some storage to the pool.
+    fn recycle(&self, mut v: Vec<u8>) {
+        // The storage may have been taken by `Vec::try_from` or
+        // `DivBuf::into_vec`, or it might have been shrunk.  Either way, it's
+        // no longer suitable.  `DivBufShared::try_into_idle` does recycle,
+        // since it drops the `Inner` normally.
+        if v.capacity() < self.capacity {
+            return;
+        }
+        v.clear();
+        if let Ok(mut free) = self.free.lock() {
+            free.push(v);
+        }
+    }
+}
+
+impl<'a> MutGuard<'a> {
+    /// Release the guard, keeping its `DivBufMut`.
+    ///
+    /// The returned `DivBufMut` retains write access until it is dropped.
+    pub fn into_inner(self) -> DivBufMut {
+        self.dbm
+    }
+}
+
+impl<'a> ops::Deref for MutGuard<'a> {
+    type Target = DivBufMut;
+
+    fn deref(&self) -> &DivBufMut {
+        &self.dbm
+    }
+}
+
+impl<'a> ops::DerefMut for MutGuard<'a> {
+    fn deref_mut(&mut self) -> &mut DivBufMut {
+        &mut self.dbm
+    }
+}
+
+#[cfg(test)]
+mod t {
+    use std::panic;
+
+    use super::*;
+
+    #[test]
+    fn add_reader_ok() {
+        let accessors = AtomicUsize::new(ONE_WRITER + 1);
+        assert_eq!(add_reader(&accessors, Relaxed), ONE_WRITER + 1);
+        assert_eq!(accessors.load(Relaxed), ONE_WRITER + 2);
+    }
+
+    /// Adding too many readers should panic, rather than silently overflow
+    /// into the writer count.
+    #[test]
+    fn add_reader_overflow() {
+        let accessors = AtomicUsize::new(READER_MASK);
+        let r = panic::catch_unwind(|| add_reader(&accessors, Relaxed));
+        assert!(r.is_err());
+        assert_eq!(accessors.load(Relaxed), READER_MASK);
+    }
+
+    /// Only buffers created by `new_zeroizing` should be wiped on drop.
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize_flag() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3]);
+        let dbs1 = DivBufShared::new_zeroizing(vec![1, 2, 3]);
+        assert!(!unsafe { (*dbs0.inner).zeroize });
+        assert!(unsafe { (*dbs1.inner).zeroize });
+    }
+}
@@ -0,0 +1,255 @@
+// vim: tw=80
+//! Parallel iteration with [`rayon`](https://docs.rs/rayon)
+
+use std::cmp;
+
+use rayon::iter::{
+    plumbing::{
+        bridge,
+        Consumer,
+        Producer,
+        ProducerCallback,
+        UnindexedConsumer,
+    },
+    IndexedParallelIterator,
+    ParallelIterator,
+};
+
+use crate::divbuf::{DivBuf, DivBufMut};
+
+/// The operations that `ChunkProducer` needs from a buffer.
+trait Window: Sized + Send {
+    fn len(&self) -> usize;
+    fn split_off(&mut self, at: usize) -> Self;
+    fn split_to(&mut self, at: usize) -> Self;
+}
+
+impl Window for DivBuf {
+    fn len(&self) -> usize {
+        DivBuf::len(self)
+    }
+
+    fn split_off(&mut self, at: usize) -> Self {
+        DivBuf::split_off(self, at)
+    }
+
+    fn split_to(&mut self, at: usize) -> Self {
+        DivBuf::split_to(self, at)
+    }
+}
+
+impl Window for DivBufMut {
+    fn len(&self) -> usize {
+        DivBufMut::len(self)
+    }
+
+    fn split_off(&mut self, at: usize) -> Self {
+        DivBufMut::split_off(self, at)
+    }
+
+    fn split_to(&mut self, at: usize) -> Self {
+        DivBufMut::split_to(self, at)
+    }
+}
+
+/// Yields chunks from either end of a buffer, and can be split in two for
+/// rayon's work stealing.
+#[derive(Debug)]
+struct ChunkProducer<T> {
+    buf:       T,
+    chunksize: usize,
+}
+
+impl<T: Window> ChunkProducer<T> {
+    fn new(buf: T, chunksize: usize) -> Self {
+        assert!(chunksize != 0);
+        ChunkProducer { buf, chunksize }
+    }
+
+    fn chunks(&self) -> usize {
+        let mut c = self.buf.len() / self.chunksize;
+        if self.buf.len() % self.chunksize != 0 {
+            c += 1;
+        }
+        c
+    }
+}
+
+impl<T: Window> Iterator for ChunkProducer<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.buf.len() == 0 {
+            None
+        } else {
+            let size = cmp::min(self.chunksize, self.buf.len());
+            Some(self.buf.split_to(size))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let c = self.chunks();
+        (c, Some(c))
+    }
+}
+
+impl<T: Window> DoubleEndedIterator for ChunkProducer<T> {
+    fn next_back(&mut self) -> Option<T> {
+        let len = self.buf.len();
+        if len == 0 {
+            None
+        } else {
+            // Only the final chunk may be short, so take the remainder from
+            // the back
+            let size = match len % self.chunksize {
+                0 => self.chunksize,
+                rem => rem,
+            };
+            Some(self.buf.split_off(len - size))
+        }
+    }
+}
+
+impl<T: Window> ExactSizeIterator for ChunkProducer<T> {}
+
+impl<T: Window> Producer for ChunkProducer<T> {
+    type IntoIter = Self;
+    type Item = T;
+
+    fn into_iter(self) -> Self {
+        self
+    }
+
+    fn split_at(mut self, index: usize) -> (Self, Self) {
+        let at = cmp::min(index.saturating_mul(self.chunksize), self.buf.len());
+        let right = self.buf.split_off(at);
+        let chunksize = self.chunksize;
+        (self, ChunkProducer::new(right, chunksize))
+    }
+}
+
+/// The return type of
+/// [`DivBuf::par_chunks`](struct.DivBuf.html#method.par_chunks)
+#[derive(Debug)]
+pub struct ParChunks(ChunkProducer<DivBuf>);
+
+impl ParallelIterator for ParChunks {
+    type Item = DivBuf;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<DivBuf>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.0.chunks())
+    }
+}
+
+impl IndexedParallelIterator for ParChunks {
+    fn drive<C: Consumer<DivBuf>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.0.chunks()
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<DivBuf>,
+    {
+        callback.callback(self.0)
+    }
+}
+
+/// The return type of
+/// [`DivBufMut::par_chunks_mut`](struct.DivBufMut.html#method.par_chunks_mut)
+#[derive(Debug)]
+pub struct ParChunksMut(ChunkProducer<DivBufMut>);
+
+impl ParallelIterator for ParChunksMut {
+    type Item = DivBufMut;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<DivBufMut>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.0.chunks())
+    }
+}
+
+impl IndexedParallelIterator for ParChunksMut {
+    fn drive<C: Consumer<DivBufMut>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.0.chunks()
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<DivBufMut>,
+    {
+        callback.callback(self.0)
+    }
+}
+
+impl DivBuf {
+    /// Break the buffer up into equal sized chunks, for processing in
+    /// parallel.
+    ///
+    /// Like [`into_chunks`], but returns a rayon `IndexedParallelIterator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// use rayon::prelude::*;
+    ///
+    /// let dbs = DivBufShared::from(vec![1; 4096]);
+    /// let db = dbs.try_const().unwrap();
+    /// let sum: usize = db
+    ///     .par_chunks(512)
+    ///     .map(|c| c.iter().map(|&x| usize::from(x)).sum::<usize>())
+    ///     .sum();
+    /// assert_eq!(sum, 4096);
+    /// ```
+    ///
+    /// [`into_chunks`]: #method.into_chunks
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn par_chunks(self, size: usize) -> ParChunks {
+        ParChunks(ChunkProducer::new(self, size))
+    }
+}
+
+impl DivBufMut {
+    /// Break the buffer up into equal sized, non-overlapping chunks, for
+    /// processing in parallel.
+    ///
+    /// Like [`into_chunks`], but returns a rayon `IndexedParallelIterator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// use rayon::prelude::*;
+    ///
+    /// let dbs = DivBufShared::from(vec![0; 4096]);
+    /// let dbm = dbs.try_mut().unwrap();
+    /// dbm.par_chunks_mut(512).for_each(|mut c| c[0] = 1);
+    /// let db = dbs.try_const().unwrap();
+    /// assert_eq!(db.iter().filter(|&&x| x == 1).count(), 8);
+    /// ```
+    ///
+    /// [`into_chunks`]: #method.into_chunks
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn par_chunks_mut(self, size: usize) -> ParChunksMut {
+        ParChunksMut(ChunkProducer::new(self, size))
+    }
+}
@@ -0,0 +1,56 @@
+// vim: tw=80
+//! Asynchronous iteration with [`futures`](https://docs.rs/futures)
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+
+use crate::divbuf::{Chunks, DivBuf};
+
+/// The return type of
+/// [`DivBuf::into_stream`](struct.DivBuf.html#method.into_stream)
+#[derive(Debug)]
+pub struct DivBufStream(Chunks);
+
+impl Stream for DivBufStream {
+    type Item = DivBuf;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<DivBuf>> {
+        // The data is already in memory, so the stream is never pending
+        Poll::Ready(self.0.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DivBuf {
+    /// Break the buffer up into equal sized chunks, yielded as a `Stream`.
+    ///
+    /// Like [`into_chunks`], but for use with asynchronous combinators.  The
+    /// stream is always ready.
+    ///
+    /// # Examples
+    /// ```
+    /// # use divbuf::*;
+    /// use futures_core::Stream;
+    ///
+    /// let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4]);
+    /// let db = dbs.try_const().unwrap();
+    /// let stream = db.into_stream(2);
+    /// assert_eq!(stream.size_hint(), (3, Some(3)));
+    /// ```
+    ///
+    /// [`into_chunks`]: #method.into_chunks
+    #[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+    pub fn into_stream(self, size: usize) -> DivBufStream {
+        DivBufStream(self.into_chunks(size))
+    }
+}
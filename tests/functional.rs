@@ -3,10 +3,13 @@
 use std::{
     borrow::{Borrow, BorrowMut},
     cmp::Ordering,
-    collections::hash_map::DefaultHasher,
-    convert::TryInto,
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap, HashSet},
+    convert::{TryFrom, TryInto},
     hash::{Hash, Hasher},
-    io::Write,
+    io::{self, Cursor, IoSlice, Read, Seek, SeekFrom, Write},
+    mem::MaybeUninit,
+    ops::Bound,
+    panic::{self, AssertUnwindSafe},
     thread,
 };
 
@@ -19,6 +22,38 @@ fn simple_hash<T: Hash>(t: &T) -> u64 {
     s.finish()
 }
 
+//
+// ByteWindow methods
+//
+mod byte_window {
+    use super::*;
+
+    /// Summarize any kind of window, without knowing its concrete type
+    fn summarize<W: ByteWindow>(w: &W) -> (usize, usize, u32) {
+        let sum = w.as_bytes().iter().map(|&x| u32::from(x)).sum();
+        (w.offset(), w.len(), sum)
+    }
+
+    #[test]
+    pub fn divbuf() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(summarize(&db), (0, 6, 21));
+        assert_eq!(summarize(&db.slice(2, 5)), (2, 3, 12));
+        assert!(ByteWindow::is_empty(&db.slice(3, 3)));
+    }
+
+    #[test]
+    pub fn divbufmut() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm = dbs.try_mut().unwrap();
+        let tail = dbm.split_off(4);
+        assert_eq!(summarize(&dbm), (0, 4, 10));
+        assert_eq!(summarize(&tail), (4, 2, 11));
+        assert!(!ByteWindow::is_empty(&tail));
+    }
+}
+
 //
 // Chunks methods
 //
@@ -35,6 +70,16 @@ mod chunks {
         assert!(chunks.next().is_none());
     }
 
+    #[test]
+    pub fn len() {
+        let dbs = DivBufShared::from((0..12).collect::<Vec<u8>>());
+        for size in 1..=13 {
+            let chunks = dbs.try_const().unwrap().into_chunks(size);
+            let len = chunks.len();
+            assert_eq!(len, chunks.count(), "size={}", size);
+        }
+    }
+
     #[test]
     #[should_panic]
     pub fn zero() {
@@ -98,6 +143,66 @@ mod chunks {
     }
 }
 
+//
+// ChunksExactMut methods
+//
+mod chunks_exact_mut {
+    use super::*;
+
+    #[test]
+    pub fn iter() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let dbm = dbs.try_mut().unwrap();
+        let mut chunks = dbm.into_chunks_exact(3);
+        assert_eq!(&chunks.next().unwrap()[..], &[1, 2, 3][..]);
+        assert_eq!(&chunks.next().unwrap()[..], &[4, 5, 6][..]);
+        assert!(chunks.next().is_none());
+        assert!(chunks.remainder().is_empty());
+    }
+
+    #[test]
+    pub fn remainder() {
+        let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        {
+            let dbm = dbs.try_mut().unwrap();
+            let mut chunks = dbm.into_chunks_exact(3);
+            let mut c0 = chunks.next().unwrap();
+            let mut c1 = chunks.next().unwrap();
+            assert!(chunks.next().is_none());
+            let mut rem = chunks.into_remainder();
+            // Each piece can be modified independently
+            c0[0] = 10;
+            c1[0] = 13;
+            rem[0] = 16;
+            rem.extend(&[8]);
+            c0.unsplit(c1).unwrap();
+            c0.unsplit(rem).unwrap();
+            assert_eq!(c0, [10, 1, 2, 13, 4, 5, 16, 7, 8][..]);
+        }
+        assert_eq!(dbs.len(), 9);
+    }
+
+    #[test]
+    pub fn size_hint() {
+        let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        let dbm = dbs.try_mut().unwrap();
+        let mut chunks = dbm.into_chunks_exact(3);
+        assert_eq!(chunks.size_hint(), (2, Some(2)));
+        chunks.next();
+        assert_eq!(chunks.size_hint(), (1, Some(1)));
+        chunks.next();
+        assert_eq!(chunks.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn zero() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let dbm = dbs.try_mut().unwrap();
+        dbm.into_chunks_exact(0);
+    }
+}
+
 //
 // ChunksMut methods
 //
@@ -114,6 +219,16 @@ mod chunks_mut {
         assert!(chunks.next().is_none());
     }
 
+    #[test]
+    pub fn len() {
+        let dbs = DivBufShared::from((0..12).collect::<Vec<u8>>());
+        for size in 1..=13 {
+            let chunks = dbs.try_mut().unwrap().into_chunks(size);
+            let len = chunks.len();
+            assert_eq!(len, chunks.count(), "size={}", size);
+        }
+    }
+
     #[test]
     #[should_panic]
     pub fn zero() {
@@ -178,239 +293,1439 @@ mod chunks_mut {
 }
 
 //
-// DivBufShared methods
+// ChunksRef methods
 //
-mod divbufshared {
+mod chunks_ref {
     use super::*;
 
     #[test]
-    pub fn cap_and_len() {
-        let mut v = Vec::<u8>::with_capacity(64);
-        v.push(0);
-        let dbs = DivBufShared::from(v);
-        assert_eq!(dbs.capacity(), 64);
-        assert_eq!(dbs.len(), 1);
+    pub fn iter() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6, 7]);
+        let db = dbs.try_const().unwrap();
+        {
+            let mut chunks = db.chunks(3);
+            assert_eq!(chunks.next().unwrap(), [1, 2, 3][..]);
+            assert_eq!(chunks.next().unwrap(), [4, 5, 6][..]);
+            assert_eq!(chunks.next().unwrap(), [7][..]);
+            assert!(chunks.next().is_none());
+        }
+        // The original DivBuf is still usable
+        assert_eq!(db, [1, 2, 3, 4, 5, 6, 7][..]);
+        let db1 = db.slice_from(5);
+        assert_eq!(db1, [6, 7][..]);
     }
 
     #[test]
-    pub fn fmt() {
-        let v = Vec::<u8>::with_capacity(64);
-        let dbs = DivBufShared::from(v);
-        let output = format!("{:?}", &dbs);
-        let expected = "DivBufShared { inner: Inner { vec: [], accessors: 0, \
-                        sharers: 1 } }";
-        assert_eq!(output, expected);
+    pub fn outlive_source() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let chunks = {
+            let db = dbs.try_const().unwrap();
+            db.chunks(2).collect::<Vec<_>>()
+        };
+        assert_eq!(chunks[0], [1, 2][..]);
+        assert_eq!(chunks[1], [3, 4][..]);
+        assert!(dbs.try_mut().is_err());
+        drop(chunks);
+        assert!(dbs.try_mut().is_ok());
     }
 
     #[test]
-    pub fn from_slice() {
-        let s = b"abcdefg";
-        let dbs = DivBufShared::from(&s[..]);
-        let mut dbm = dbs.try_mut().unwrap();
-        assert_eq!(dbm, s[..]);
-        // dbs should've been copy constructed, so we can mutate it without changing
-        // the original slice
-        dbm[0] = b'A';
-        assert_ne!(dbm, s[..]);
+    pub fn size_hint() {
+        let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5, 6]);
+        let db = dbs.try_const().unwrap();
+        let mut chunks = db.chunks(3);
+        assert_eq!(chunks.size_hint(), (3, Some(3)));
+        chunks.next();
+        assert_eq!(chunks.size_hint(), (2, Some(2)));
+        chunks.next();
+        chunks.next();
+        assert_eq!(chunks.size_hint(), (0, Some(0)));
     }
 
     #[test]
-    pub fn is_empty() {
-        assert!(DivBufShared::with_capacity(4096).is_empty());
-        assert!(!DivBufShared::from(vec![1, 2, 3]).is_empty());
+    #[should_panic]
+    pub fn zero() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let db = dbs.try_const().unwrap();
+        db.chunks(0);
     }
+}
+
+//
+// IndexedChunks methods
+//
+mod indexed_chunks {
+    use super::*;
 
     #[test]
-    pub fn send() {
-        let dbs = DivBufShared::with_capacity(4096);
-        thread::spawn(move || {
-            let _ = dbs;
-        })
-        .join()
-        .unwrap();
+    pub fn iter() {
+        let dbs = DivBufShared::from((0..10).collect::<Vec<u8>>());
+        // The offsets are relative to the DivBuf, not the DivBufShared
+        let db = dbs.try_const().unwrap().slice_from(2);
+        let mut chunks = db.into_indexed_chunks(3);
+        let (offset, chunk) = chunks.next().unwrap();
+        assert_eq!(offset, 0);
+        assert_eq!(chunk, [2, 3, 4][..]);
+        let (offset, chunk) = chunks.next().unwrap();
+        assert_eq!(offset, 3);
+        assert_eq!(chunk, [5, 6, 7][..]);
+        let (offset, chunk) = chunks.next().unwrap();
+        assert_eq!(offset, 6);
+        assert_eq!(chunk, [8, 9][..]);
+        assert!(chunks.next().is_none());
     }
 
     #[test]
-    pub fn sync() {
-        lazy_static! {
-            pub static ref DBS: DivBufShared =
-                DivBufShared::from(vec![0; 4096]);
-        }
-        let r = &DBS;
-        thread::spawn(move || {
-            let _ = r;
-        })
-        .join()
-        .unwrap();
+    pub fn size_hint() {
+        let dbs = DivBufShared::from((0..10).collect::<Vec<u8>>());
+        let mut chunks = dbs.try_const().unwrap().into_indexed_chunks(4);
+        assert_eq!(chunks.size_hint(), (3, Some(3)));
+        chunks.next();
+        assert_eq!(chunks.size_hint(), (2, Some(2)));
     }
 
     #[test]
-    pub fn try_const() {
-        let dbs = DivBufShared::with_capacity(4096);
-        // Create an initial DivBuf
-        let _db0 = dbs.try_const().unwrap();
-        // Creating a second is allowed, too
-        let _db1 = dbs.try_const().unwrap();
+    #[should_panic]
+    pub fn zero() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let db = dbs.try_const().unwrap();
+        db.into_indexed_chunks(0);
     }
+}
 
-    #[test]
-    pub fn try_const_after_try_mut() {
-        let dbs = DivBufShared::with_capacity(4096);
-        // Create an initial DivBufMut
-        let _dbm = dbs.try_mut().unwrap();
-        // Creating a DivBuf should fail, because there are writers
-        assert!(dbs.try_const().is_err());
+//
+// Split methods
+//
+mod split {
+    use super::*;
+
+    fn pieces(dbs: &DivBufShared) -> Vec<Vec<u8>> {
+        let db = dbs.try_const().unwrap();
+        db.split(b',').map(|p| p.to_vec()).collect()
     }
 
     #[test]
-    pub fn try_mut() {
-        let dbs = DivBufShared::with_capacity(4096);
-        // Create an initial DivBufMut
-        let _dbm0 = dbs.try_mut().unwrap();
-        // Creating a second is not allowed
-        assert!(dbs.try_mut().is_err());
+    pub fn consecutive_delimiters() {
+        let dbs = DivBufShared::from(&b"a,,b"[..]);
+        assert_eq!(pieces(&dbs), vec![b"a".to_vec(), vec![], b"b".to_vec()]);
     }
 
     #[test]
-    pub fn try_mut_after_try_const() {
-        let dbs = DivBufShared::with_capacity(4096);
-        // Create an initial DivBuf
-        let _db0 = dbs.try_const().unwrap();
-        // Now creating a mutable buffer is not allowed
-        assert!(dbs.try_mut().is_err());
+    pub fn empty() {
+        let dbs = DivBufShared::from(vec![]);
+        assert_eq!(pieces(&dbs), vec![Vec::<u8>::new()]);
     }
 
-    #[cfg(feature = "experimental")]
     #[test]
-    pub fn uninitialized() {
-        let cap = 4096;
-        let dbs = DivBufShared::uninitialized(cap);
-        assert_eq!(dbs.capacity(), cap);
-        assert_eq!(dbs.len(), cap);
+    pub fn leading_delimiter() {
+        let dbs = DivBufShared::from(&b",a"[..]);
+        assert_eq!(pieces(&dbs), vec![vec![], b"a".to_vec()]);
     }
 
     #[test]
-    pub fn to_vec() {
-        let v = vec![1, 2, 3, 4];
-        let dbs = DivBufShared::from(v);
-
-        {
-            let mut dbm = dbs.try_mut().unwrap();
-            assert_eq!(dbm, [1, 2, 3, 4][..]);
-            dbm[0] = 5;
-            assert_eq!(dbm, [5, 2, 3, 4][..]);
-        }
-
-        let v2: Vec<u8> = dbs.try_into().unwrap();
-        assert_eq!(v2, vec![5, 2, 3, 4]);
+    pub fn no_delimiters() {
+        let dbs = DivBufShared::from(&b"abc"[..]);
+        assert_eq!(pieces(&dbs), vec![b"abc".to_vec()]);
     }
 
     #[test]
-    pub fn to_vec_after_try_mut() {
-        let v = vec![1, 2, 3, 4];
-        let dbs = DivBufShared::from(v);
-
-        let _dbm = dbs.try_mut().unwrap();
-        let maybe_v: Result<Vec<u8>, _> = dbs.try_into();
-        assert!(maybe_v.is_err());
+    pub fn only_delimiters() {
+        let dbs = DivBufShared::from(&b",,"[..]);
+        assert_eq!(pieces(&dbs), vec![Vec::<u8>::new(); 3]);
     }
 
+    /// Should produce the same pieces as the slice method
     #[test]
-    pub fn to_vec_after_try_const() {
-        let v = vec![1, 2, 3, 4];
-        let dbs = DivBufShared::from(v);
-
-        let _db = dbs.try_const().unwrap();
-        let maybe_v: Result<Vec<u8>, _> = dbs.try_into();
-        assert!(maybe_v.is_err());
+    pub fn same_as_slice() {
+        let s = b",one,,two,three,";
+        let dbs = DivBufShared::from(&s[..]);
+        let expected = s.split(|&b| b == b',').map(|p| p.to_vec());
+        assert_eq!(pieces(&dbs), expected.collect::<Vec<_>>());
     }
 
     #[test]
-    pub fn to_vec_after_clone_inaccessible() {
-        let v = vec![1, 2, 3, 4];
-        let dbs = DivBufShared::from(v);
-
-        let dbm = dbs.try_mut().unwrap();
-        let _dbi = dbm.clone_inaccessible();
-        drop(dbm);
-
-        let maybe_v: Result<Vec<u8>, _> = dbs.try_into();
-        assert!(maybe_v.is_err());
+    pub fn trailing_delimiter() {
+        let dbs = DivBufShared::from(&b"a,"[..]);
+        assert_eq!(pieces(&dbs), vec![b"a".to_vec(), vec![]]);
     }
 }
 
 //
-// DivBuf methods
+// SplitInclusive methods
 //
-mod divbuf_ {
+mod split_inclusive {
     use super::*;
 
+    fn pieces(db: DivBuf) -> Vec<Vec<u8>> {
+        db.split_inclusive(b'\n').map(|p| p.to_vec()).collect()
+    }
+
     #[test]
-    pub fn as_ref() {
-        let dbs = DivBufShared::from(vec![1, 2, 3]);
-        let db0 = dbs.try_const().unwrap();
-        let s: &[u8] = db0.as_ref();
-        assert_eq!(s, &[1, 2, 3]);
+    pub fn consecutive_delimiters() {
+        let dbs = DivBufShared::from(&b"\n\na\n"[..]);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(
+            pieces(db),
+            vec![b"\n".to_vec(), b"\n".to_vec(), b"a\n".to_vec()]
+        );
     }
 
     #[test]
-    pub fn as_ref_empty() {
+    pub fn empty() {
         let dbs = DivBufShared::from(vec![]);
-        let db0 = dbs.try_const().unwrap();
-        let s: &[u8] = db0.as_ref();
-        assert_eq!(s, &[]);
+        let db = dbs.try_const().unwrap();
+        assert!(pieces(db).is_empty());
     }
 
     #[test]
-    pub fn borrow() {
-        let dbs = DivBufShared::from(vec![1, 2, 3]);
-        let db0 = dbs.try_const().unwrap();
-        let s: &[u8] = db0.borrow();
-        assert_eq!(s, &[1, 2, 3]);
+    pub fn no_trailing_delimiter() {
+        let dbs = DivBufShared::from(&b"one\ntwo"[..]);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(pieces(db), vec![b"one\n".to_vec(), b"two".to_vec()]);
     }
 
     #[test]
-    pub fn clone() {
-        let dbs = DivBufShared::from(vec![1, 2, 3]);
-        let db0 = dbs.try_const().unwrap();
-        let mut db1 = db0.clone();
-        assert_eq!(db0, db1);
-        // We should be able to modify one DivBuf without affecting the other
-        db1.split_off(1);
-        assert_ne!(db0, db1);
+    pub fn trailing_delimiter() {
+        let dbs = DivBufShared::from(&b"one\ntwo\n"[..]);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(pieces(db), vec![b"one\n".to_vec(), b"two\n".to_vec()]);
     }
 
+    /// The pieces should share storage with the original buffer
     #[test]
-    pub fn clone_inaccessible() {
-        let dbs = DivBufShared::from(vec![1, 2, 3]);
-        let dbm = dbs.try_mut().unwrap();
-        let _dbi: DivBufInaccessible = dbm.clone_inaccessible();
+    pub fn zero_copy() {
+        let dbs = DivBufShared::from(&b"one\ntwo"[..]);
+        let db = dbs.try_const().unwrap();
+        let ptr = db.as_ptr();
+        let mut iter = db.split_inclusive(b'\n');
+        assert_eq!(iter.next().unwrap().as_ptr(), ptr);
+        assert_eq!(iter.next().unwrap().as_ptr(), ptr.wrapping_add(4));
     }
+}
+
+//
+// ParChunks and ParChunksMut methods
+//
+#[cfg(feature = "rayon")]
+mod par_chunks {
+    use rayon::prelude::*;
+
+    use super::*;
 
     #[test]
-    pub fn deref() {
-        let dbs = DivBufShared::from(vec![1, 2, 3]);
-        let db = dbs.try_const().unwrap();
-        let slice: &[u8] = &db;
-        assert_eq!(slice, &[1, 2, 3]);
+    pub fn sum() {
+        let v = (0..10_000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let expected: u64 = v.iter().map(|&x| u64::from(x)).sum();
+        let dbs = DivBufShared::from(v);
+        for &size in &[1, 7, 512, 9_999, 10_000, 20_000] {
+            let db = dbs.try_const().unwrap();
+            let sum: u64 = db
+                .par_chunks(size)
+                .map(|c| c.iter().map(|&x| u64::from(x)).sum::<u64>())
+                .sum();
+            assert_eq!(sum, expected);
+        }
     }
 
     #[test]
-    pub fn deref_empty() {
-        let dbs = DivBufShared::from(vec![]);
+    pub fn collect() {
+        let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5, 6]);
         let db = dbs.try_const().unwrap();
-        let slice: &[u8] = &db;
-        assert_eq!(slice, &[]);
+        let par = db.par_chunks(3);
+        assert_eq!(par.len(), 3);
+        let chunks = par.collect::<Vec<_>>();
+        assert_eq!(chunks[0], [0, 1, 2][..]);
+        assert_eq!(chunks[1], [3, 4, 5][..]);
+        assert_eq!(chunks[2], [6][..]);
+        // Reverse iteration requires DoubleEndedIterator on the producer
+        let db = dbs.try_const().unwrap();
+        let rev = db.par_chunks(3).rev().collect::<Vec<_>>();
+        assert_eq!(rev[0], [6][..]);
+        assert_eq!(rev[2], [0, 1, 2][..]);
     }
 
-    // A DivBuf should be able to own its storage, and will free it on last drop
     #[test]
-    pub fn drop_last() {
+    pub fn mutate() {
+        let dbs = DivBufShared::from(vec![0u8; 10_000]);
+        let dbm = dbs.try_mut().unwrap();
+        dbm.par_chunks_mut(7).enumerate().for_each(|(i, mut c)| {
+            for x in c.iter_mut() {
+                *x = (i % 256) as u8;
+            }
+        });
+        let db = dbs.try_const().unwrap();
+        for (i, &x) in db.iter().enumerate() {
+            assert_eq!(x, (i / 7 % 256) as u8);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn zero() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let db = dbs.try_const().unwrap();
+        db.par_chunks(0);
+    }
+}
+
+//
+// DivBufStream methods
+//
+#[cfg(feature = "futures")]
+mod divbuf_stream {
+    use futures::{executor::block_on, Stream, StreamExt};
+
+    use super::*;
+
+    #[test]
+    pub fn stream() {
+        let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5, 6]);
+        let db = dbs.try_const().unwrap();
+        let stream = db.into_stream(3);
+        assert_eq!(stream.size_hint(), (3, Some(3)));
+        let streamed = block_on(stream.collect::<Vec<_>>());
+        let chunks =
+            dbs.try_const().unwrap().into_chunks(3).collect::<Vec<_>>();
+        assert_eq!(streamed, chunks);
+    }
+
+    #[test]
+    pub fn empty() {
+        let dbs = DivBufShared::from(vec![]);
+        let db = dbs.try_const().unwrap();
+        let streamed = block_on(db.into_stream(3).collect::<Vec<_>>());
+        assert!(streamed.is_empty());
+    }
+}
+
+//
+// DivBufBuilder methods
+//
+mod divbuf_builder {
+    use super::*;
+
+    #[test]
+    pub fn empty() {
+        let builder = DivBufBuilder::new();
+        assert!(builder.is_empty());
+        let dbs = builder.finish();
+        assert!(dbs.is_empty());
+    }
+
+    #[test]
+    pub fn push_slice() {
+        let mut builder = DivBufBuilder::with_capacity(4);
+        builder.push_slice(&[1, 2]);
+        builder.push_slice(&[]);
+        builder.push_slice(&[3, 4, 5]);
+        builder.push_slice(&[6]);
+        assert_eq!(builder.len(), 6);
+        let dbs = builder.finish();
+        assert_eq!(dbs.try_const().unwrap(), [1, 2, 3, 4, 5, 6][..]);
+    }
+
+    /// Pushing many small slices should reallocate only a logarithmic number
+    /// of times.
+    #[test]
+    pub fn amortized_growth() {
+        let mut builder = DivBufBuilder::new();
+        let mut reallocs = 0;
+        let mut capacity = builder.capacity();
+        for i in 0..10_000u32 {
+            builder.push_slice(&i.to_le_bytes()[..1]);
+            if builder.capacity() != capacity {
+                reallocs += 1;
+                capacity = builder.capacity();
+            }
+        }
+        assert_eq!(builder.len(), 10_000);
+        assert!(reallocs < 20, "Reallocated {} times", reallocs);
+    }
+}
+
+//
+// DivBufCursor methods
+//
+mod divbuf_cursor {
+    use super::*;
+
+    #[test]
+    pub fn into_inner() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let mut cursor = DivBufCursor::new(dbs.try_const().unwrap());
+        cursor.read_u8();
+        assert_eq!(cursor.get_ref(), &[1, 2, 3][..]);
+        assert_eq!(cursor.into_inner(), [1, 2, 3][..]);
+    }
+
+    #[test]
+    pub fn peek() {
+        let dbs = DivBufShared::from(vec![1, 2]);
+        let mut cursor = DivBufCursor::new(dbs.try_const().unwrap());
+        assert_eq!(cursor.peek(), Some(1));
+        assert_eq!(cursor.peek(), Some(1));
+        assert_eq!(cursor.position(), 0);
+        cursor.set_position(2);
+        assert_eq!(cursor.peek(), None);
+    }
+
+    #[test]
+    pub fn read_u8() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let mut db = dbs.try_const().unwrap();
+        // The cursor should work on a window that doesn't start at 0
+        let mut cursor = DivBufCursor::new(db.split_off(1));
+        assert_eq!(cursor.remaining(), 3);
+        assert_eq!(cursor.read_u8(), Some(2));
+        assert_eq!(cursor.read_u8(), Some(3));
+        assert_eq!(cursor.read_u8(), Some(4));
+        assert_eq!(cursor.remaining(), 0);
+        assert_eq!(cursor.read_u8(), None);
+        assert_eq!(cursor.position(), 3);
+    }
+
+    #[test]
+    pub fn rewind() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let mut cursor = DivBufCursor::new(dbs.try_const().unwrap());
+        cursor.read_u8();
+        cursor.read_u8();
+        cursor.rewind();
+        assert_eq!(cursor.position(), 0);
+        assert_eq!(cursor.read_u8(), Some(1));
+    }
+
+    #[test]
+    pub fn seek() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let mut cursor = DivBufCursor::new(dbs.try_const().unwrap());
+        assert_eq!(cursor.seek(3).unwrap(), 3);
+        assert_eq!(cursor.read_u8(), Some(4));
+        // Seek backwards
+        assert_eq!(cursor.seek(-3).unwrap(), 1);
+        assert_eq!(cursor.read_u8(), Some(2));
+        // Seek to the very end
+        assert_eq!(cursor.seek(2).unwrap(), 4);
+        assert_eq!(cursor.remaining(), 0);
+    }
+
+    #[test]
+    pub fn seek_out_of_bounds() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let mut cursor = DivBufCursor::new(dbs.try_const().unwrap());
+        cursor.set_position(2);
+        assert!(cursor.seek(-3).is_err());
+        assert!(cursor.seek(3).is_err());
+        assert!(cursor.seek(isize::MIN).is_err());
+        assert_eq!(cursor.position(), 2);
+    }
+
+    #[test]
+    pub fn set_position() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let mut cursor = DivBufCursor::new(dbs.try_const().unwrap());
+        cursor.set_position(2);
+        assert_eq!(cursor.read_u8(), Some(3));
+        cursor.set_position(10);
+        assert_eq!(cursor.remaining(), 0);
+        assert_eq!(cursor.read_u8(), None);
+    }
+}
+
+//
+// DivBufShared methods
+//
+mod divbufshared {
+    use super::*;
+
+    #[test]
+    pub fn cap_and_len() {
+        let mut v = Vec::<u8>::with_capacity(64);
+        v.push(0);
+        let dbs = DivBufShared::from(v);
+        assert_eq!(dbs.capacity(), 64);
+        assert_eq!(dbs.len(), 1);
+    }
+
+    #[test]
+    pub fn clone() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3]);
+        let dbs1 = dbs0.clone();
+        assert_eq!(dbs0, dbs1);
+        dbs1.try_mut().unwrap()[0] = 4;
+        assert_eq!(dbs0.try_const().unwrap(), [4, 2, 3][..]);
+        // Both handles share a single lock
+        let _db = dbs0.try_const().unwrap();
+        assert!(dbs1.try_mut().is_err());
+    }
+
+    /// The storage should outlive the original handle
+    #[test]
+    pub fn clone_outlives_original() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3]);
+        let dbs1 = dbs0.clone();
+        assert!(!dbs1.is_idle());
+        drop(dbs0);
+        assert!(dbs1.is_idle());
+        dbs1.try_mut().unwrap().extend(&[4]);
+        assert_eq!(Vec::try_from(dbs1).unwrap(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    pub fn copy_from_slice() {
+        let dbs = DivBufShared::with_capacity(64);
+        dbs.copy_from_slice(&b"first payload"[..]).unwrap();
+        assert_eq!(dbs.try_const().unwrap(), b"first payload"[..]);
+        dbs.copy_from_slice(&b"second"[..]).unwrap();
+        assert_eq!(dbs.try_const().unwrap(), b"second"[..]);
+        assert_eq!(dbs.capacity(), 64);
+    }
+
+    #[test]
+    pub fn copy_from_slice_with_accessors() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        {
+            let _db = dbs.try_const().unwrap();
+            assert!(dbs.copy_from_slice(&[4, 5][..]).is_err());
+        }
+        {
+            let _dbm = dbs.try_mut().unwrap();
+            assert!(dbs.copy_from_slice(&[4, 5][..]).is_err());
+        }
+        assert_eq!(dbs.try_const().unwrap(), [1, 2, 3][..]);
+    }
+
+    #[test]
+    pub fn eq() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3]);
+        let dbs1 = DivBufShared::from(vec![1, 2, 3]);
+        let dbs2 = DivBufShared::from(vec![1, 2, 4]);
+        assert_eq!(dbs0, dbs1);
+        assert_eq!(simple_hash(&dbs0), simple_hash(&dbs1));
+        assert_ne!(dbs0, dbs2);
+        // Readers don't prevent comparison
+        let _db = dbs0.try_const().unwrap();
+        assert_eq!(dbs0, dbs1);
+    }
+
+    #[test]
+    pub fn eq_with_writer() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3]);
+        let dbs1 = DivBufShared::from(vec![1, 2, 3]);
+        let dbm = dbs0.try_mut().unwrap();
+        assert_ne!(dbs0, dbs1);
+        // A buffer still equals itself
+        assert_eq!(dbs0, dbs0);
+        drop(dbm);
+        assert_eq!(dbs0, dbs1);
+    }
+
+    #[test]
+    pub fn hashmap_key() {
+        let mut map = HashMap::new();
+        map.insert(DivBufShared::from(vec![1, 2, 3]), 0);
+        map.insert(DivBufShared::from(vec![4, 5, 6]), 1);
+        assert_eq!(map.get(&DivBufShared::from(vec![4, 5, 6])), Some(&1));
+        assert!(!map.contains_key(&DivBufShared::from(vec![7])));
+    }
+
+    #[test]
+    pub fn fmt() {
+        let v = Vec::<u8>::with_capacity(64);
+        let dbs = DivBufShared::from(v);
+        let output = format!("{:?}", dbs);
+        #[cfg(not(feature = "zeroize"))]
+        let expected = "DivBufShared { inner: Inner { vec: [], len: 0, \
+                        capacity: 64, accessors: 0, sharers: 1 } }";
+        #[cfg(feature = "zeroize")]
+        let expected = "DivBufShared { inner: Inner { vec: [], len: 0, \
+                        capacity: 64, accessors: 0, sharers: 1, zeroize: \
+                        false } }";
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    pub fn from_divbuf() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let db = dbs0.try_const().unwrap().slice(2, 5);
+        let dbs1 = DivBufShared::from(&db);
+        assert_eq!(dbs1.len(), 3);
+        {
+            // The new buffer has its own storage, so it can be mutated even
+            // while the original is being read.
+            let mut dbm = dbs1.try_mut().unwrap();
+            assert_eq!(dbm, [3, 4, 5][..]);
+            dbm[0] = 0;
+        }
+        assert_eq!(db, [3, 4, 5][..]);
+        drop(db);
+        drop(dbs0);
+        assert_eq!(dbs1.try_const().unwrap(), [0, 4, 5][..]);
+    }
+
+    #[test]
+    pub fn from_iter() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3, 4]);
+        let dbs1 = DivBufShared::from(vec![5, 6]);
+        let dbs2 = DivBufShared::with_capacity(16);
+        dbs2.try_mut().unwrap().extend(&[7, 8, 9]);
+        let mut db0 = dbs0.try_const().unwrap();
+        let pieces = vec![
+            db0.split_off(2),
+            dbs1.try_const().unwrap(),
+            db0,
+            dbs2.try_const().unwrap().slice_from(1),
+        ];
+        let merged: DivBufShared = pieces.into_iter().collect();
+        assert_eq!(merged.try_const().unwrap(), [3, 4, 5, 6, 1, 2, 8, 9][..]);
+        // The pieces were copied, so the sources are no longer in use
+        assert!(dbs0.try_mut().is_ok());
+    }
+
+    #[test]
+    pub fn from_iter_empty() {
+        let merged: DivBufShared = Vec::<DivBuf>::new().into_iter().collect();
+        assert!(merged.is_empty());
+    }
+
+    #[test]
+    pub fn from_slice() {
+        let s = b"abcdefg";
+        let dbs = DivBufShared::from(&s[..]);
+        let mut dbm = dbs.try_mut().unwrap();
+        assert_eq!(dbm, s[..]);
+        // dbs should've been copy constructed, so we can mutate it without changing
+        // the original slice
+        dbm[0] = b'A';
+        assert_ne!(dbm, s[..]);
+    }
+
+    #[test]
+    pub fn from_exact_iter() {
+        let dbs = DivBufShared::from_exact_iter((0..1000).map(|x| x as u8));
+        assert_eq!(dbs.len(), 1000);
+        assert_eq!(dbs.capacity(), 1000);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db[0], 0);
+        assert_eq!(db[999], (999 % 256) as u8);
+    }
+
+    #[test]
+    pub fn from_vec_filled() {
+        let mut v = Vec::<u8>::with_capacity(64);
+        v.extend_from_slice(&[1, 2, 3]);
+        let capacity = v.capacity();
+        let dbs = DivBufShared::from_vec_filled(v, 0xa5);
+        assert_eq!(dbs.len(), capacity);
+        assert_eq!(dbs.capacity(), capacity);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(&db[0..3], &[1, 2, 3][..]);
+        assert!(db[3..].iter().all(|&x| x == 0xa5));
+    }
+
+    #[test]
+    pub fn from_vec_filled_full() {
+        let v = vec![1, 2, 3];
+        let capacity = v.capacity();
+        let dbs = DivBufShared::from_vec_filled(v, 0);
+        assert_eq!(dbs.len(), capacity);
+        assert_eq!(&dbs.try_const().unwrap()[0..3], &[1, 2, 3][..]);
+    }
+
+    #[test]
+    pub fn from_vec_prefix() {
+        let v = (0..64).collect::<Vec<u8>>();
+        let capacity = v.capacity();
+        let dbs = DivBufShared::from_vec_prefix(v, 10);
+        assert_eq!(dbs.len(), 10);
+        assert_eq!(dbs.capacity(), capacity);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(&db[..], &(0..10).collect::<Vec<u8>>()[..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't take a prefix past the end")]
+    pub fn from_vec_prefix_past_end() {
+        DivBufShared::from_vec_prefix(vec![1, 2, 3], 4);
+    }
+
+    #[test]
+    pub fn is_empty() {
+        assert!(DivBufShared::with_capacity(4096).is_empty());
+        assert!(!DivBufShared::from(vec![1, 2, 3]).is_empty());
+    }
+
+    // len and capacity may be queried while another thread extends the buffer
+    #[test]
+    pub fn is_idle_and_is_writable() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        assert!(dbs.is_idle());
+        assert!(dbs.is_writable());
+        {
+            let db0 = dbs.try_const().unwrap();
+            let _db1 = db0.slice(0, 2);
+            assert!(!dbs.is_idle());
+            assert!(!dbs.is_writable());
+        }
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            let _dbm1 = dbm0.split_off(2);
+            assert!(!dbs.is_idle());
+            assert!(!dbs.is_writable());
+        }
+        let dbi = dbs.try_const().unwrap().clone_inaccessible();
+        // DivBufInaccessibles keep the storage alive, but don't block writers
+        assert!(!dbs.is_idle());
+        assert!(dbs.is_writable());
+        assert_eq!(dbs.is_writable(), dbs.try_mut().is_ok());
+        drop(dbi);
+        assert!(dbs.is_idle());
+        assert!(dbs.is_writable());
+        // Neither do clones of the DivBufShared
+        let dbs1 = dbs.clone();
+        assert!(!dbs.is_idle());
+        assert!(dbs.is_writable());
+        drop(dbs1);
+        assert!(dbs.is_idle());
+    }
+
+    #[test]
+    pub fn len_during_extend() {
+        lazy_static! {
+            pub static ref DBS: DivBufShared = DivBufShared::with_capacity(0);
+        }
+        let mut dbm = DBS.try_mut().unwrap();
+        let t = thread::spawn(move || {
+            for i in 0..1000u32 {
+                dbm.extend(i.to_le_bytes().iter());
+            }
+        });
+        let mut oldlen = 0;
+        while oldlen < 4000 {
+            let len = DBS.len();
+            assert!(len >= oldlen);
+            assert!(len <= 4000);
+            assert!(len % 4 == 0);
+            oldlen = len;
+            let _ = DBS.capacity();
+        }
+        t.join().unwrap();
+        assert!(DBS.capacity() >= 4000);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    pub fn new_zeroizing() {
+        let dbs = DivBufShared::new_zeroizing(vec![1, 2, 3]);
+        {
+            let mut dbm = dbs.try_mut().unwrap();
+            dbm.extend(&[4, 5]);
+        }
+        let db = dbs.try_const().unwrap();
+        drop(dbs);
+        // The storage must not be wiped until the last child is gone
+        assert_eq!(db, [1, 2, 3, 4, 5][..]);
+    }
+
+    /// try_into_idle must free a zeroizing buffer through the normal path, not
+    /// by extracting its Vec.
+    #[cfg(feature = "zeroize")]
+    #[test]
+    pub fn new_zeroizing_try_into_idle() {
+        let dbs = DivBufShared::new_zeroizing(b"hunter2".to_vec());
+        let db = dbs.try_const().unwrap();
+        let dbs = dbs.try_into_idle().unwrap_err();
+        assert_eq!(db, b"hunter2"[..]);
+        drop(db);
+        assert!(dbs.try_into_idle().is_ok());
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    pub fn realloc_count() {
+        let dbs = DivBufShared::with_capacity(4);
+        let capacity = dbs.capacity();
+        let mut dbm = dbs.try_mut().unwrap();
+        dbm.extend(std::iter::repeat(&0).take(capacity));
+        let mut dbm1 = dbm.split_off(2);
+        let _ = dbm.split_to(1);
+        // Neither splitting nor filling the existing capacity reallocates
+        assert_eq!(dbs.realloc_count(), 0);
+        dbm1.extend(&[1]);
+        assert_eq!(dbs.realloc_count(), 1);
+    }
+
+    #[test]
+    pub fn reset() {
+        let dbs = DivBufShared::with_capacity(64);
+        dbs.try_mut().unwrap().extend(&[1, 2, 3]);
+        let capacity = dbs.capacity();
+        dbs.reset().unwrap();
+        assert!(dbs.is_empty());
+        assert_eq!(dbs.capacity(), capacity);
+        // It can be refilled
+        dbs.try_mut().unwrap().extend(&[4, 5]);
+        assert_eq!(dbs.try_const().unwrap(), [4, 5][..]);
+    }
+
+    #[test]
+    pub fn reset_with_accessors() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        {
+            let _db = dbs.try_const().unwrap();
+            assert!(dbs.reset().is_err());
+        }
+        {
+            let _dbm = dbs.try_mut().unwrap();
+            assert!(dbs.reset().is_err());
+        }
+        assert_eq!(dbs.len(), 3);
+        // An inaccessible handle doesn't prevent resetting
+        let _dbi = dbs.try_const().unwrap().clone_inaccessible();
+        dbs.reset().unwrap();
+        assert!(dbs.is_empty());
+    }
+
+    #[test]
+    pub fn resize_grow() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        dbs.resize(5, 9).unwrap();
+        assert_eq!(dbs.len(), 5);
+        assert_eq!(dbs.try_const().unwrap(), [1, 2, 3, 9, 9][..]);
+    }
+
+    #[test]
+    pub fn resize_shrink() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        dbs.resize(1, 9).unwrap();
+        assert_eq!(dbs.len(), 1);
+        assert_eq!(dbs.try_const().unwrap(), [1][..]);
+    }
+
+    #[test]
+    pub fn resize_with_accessors() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        {
+            let _db = dbs.try_const().unwrap();
+            assert!(dbs.resize(5, 0).is_err());
+        }
+        {
+            let _dbm = dbs.try_mut().unwrap();
+            assert!(dbs.resize(0, 0).is_err());
+        }
+        assert_eq!(dbs.try_const().unwrap(), [1, 2, 3][..]);
+    }
+
+    #[test]
+    pub fn send() {
+        let dbs = DivBufShared::with_capacity(4096);
+        thread::spawn(move || {
+            let _ = dbs;
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    pub fn spare_capacity() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        {
+            let mut dbm = dbs.try_mut().unwrap();
+            dbm.reserve(100);
+        }
+        assert!(dbs.spare_capacity() >= 100);
+        assert_eq!(dbs.spare_capacity(), dbs.capacity() - 3);
+        dbs.try_mut().unwrap().extend(&[4, 5]);
+        assert_eq!(dbs.spare_capacity(), dbs.capacity() - 5);
+    }
+
+    #[test]
+    pub fn sync() {
+        lazy_static! {
+            pub static ref DBS: DivBufShared =
+                DivBufShared::from(vec![0; 4096]);
+        }
+        let r = &DBS;
+        thread::spawn(move || {
+            let _ = r;
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    pub fn try_const() {
+        let dbs = DivBufShared::with_capacity(4096);
+        // Create an initial DivBuf
+        let _db0 = dbs.try_const().unwrap();
+        // Creating a second is allowed, too
+        let _db1 = dbs.try_const().unwrap();
+    }
+
+    #[test]
+    pub fn try_const_after_try_mut() {
+        let dbs = DivBufShared::with_capacity(4096);
+        // Create an initial DivBufMut
+        let _dbm = dbs.try_mut().unwrap();
+        // Creating a DivBuf should fail, because there are writers
+        assert!(dbs.try_const().is_err());
+    }
+
+    #[test]
+    pub fn try_const_range() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let db0 = dbs.try_const_range(2, 5).unwrap();
+        assert_eq!(db0, [3, 4, 5][..]);
+        let db1 = dbs.try_const_range(0, 6).unwrap();
+        assert_eq!(db1, [1, 2, 3, 4, 5, 6][..]);
+        let db2 = dbs.try_const_range(6, 6).unwrap();
+        assert!(db2.is_empty());
+        // Each DivBuf must be a separate reader
+        drop(db0);
+        drop(db1);
+        assert!(db2.try_mut().is_ok());
+    }
+
+    #[test]
+    pub fn try_const_range_after_try_mut() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let _dbm = dbs.try_mut().unwrap();
+        assert!(dbs.try_const_range(0, 2).is_err());
+    }
+
+    #[test]
+    pub fn try_const_range_out_of_bounds() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        assert!(dbs.try_const_range(2, 7).is_err());
+        assert!(dbs.try_const_range(3, 2).is_err());
+        // Failed attempts must not leave any readers behind
+        assert!(dbs.try_mut().is_ok());
+    }
+
+    #[test]
+    pub fn try_into_idle() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let db = dbs.try_const().unwrap();
+        drop(db);
+        assert!(dbs.try_into_idle().is_ok());
+    }
+
+    #[test]
+    pub fn try_into_idle_busy() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let dbm = dbs.try_mut().unwrap();
+        let dbs = dbs.try_into_idle().unwrap_err();
+        let dbi = dbm.clone_inaccessible();
+        drop(dbm);
+        // A DivBufInaccessible also keeps the storage alive
+        let dbs = dbs.try_into_idle().unwrap_err();
+        drop(dbi);
+        assert!(dbs.try_into_idle().is_ok());
+    }
+
+    #[test]
+    pub fn try_mut() {
+        let dbs = DivBufShared::with_capacity(4096);
+        // Create an initial DivBufMut
+        let _dbm0 = dbs.try_mut().unwrap();
+        // Creating a second is not allowed
+        assert!(dbs.try_mut().is_err());
+    }
+
+    #[test]
+    pub fn try_mut_after_try_const() {
+        let dbs = DivBufShared::with_capacity(4096);
+        // Create an initial DivBuf
+        let _db0 = dbs.try_const().unwrap();
+        // Now creating a mutable buffer is not allowed
+        assert!(dbs.try_mut().is_err());
+    }
+
+    #[test]
+    pub fn try_mut_range() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        {
+            let mut dbm = dbs.try_mut_range(2, 5).unwrap();
+            assert_eq!(dbm, [3, 4, 5][..]);
+            dbm[0] = 0;
+            // A narrowed DivBufMut is still exclusive
+            assert!(dbs.try_const().is_err());
+            assert!(dbs.try_mut_range(0, 1).is_err());
+            // And it isn't terminal
+            assert!(dbm.try_extend([7].iter()).is_err());
+        }
+        assert_eq!(dbs.try_const().unwrap(), [1, 2, 0, 4, 5, 6][..]);
+    }
+
+    #[test]
+    pub fn try_mut_range_out_of_bounds() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        assert!(dbs.try_mut_range(2, 7).is_err());
+        assert!(dbs.try_mut_range(3, 2).is_err());
+        // Failed attempts must not leave any writers behind
+        assert!(dbs.try_mut().is_ok());
+    }
+
+    #[test]
+    pub fn try_mut_ranges() {
+        let dbs = DivBufShared::from(vec![0; 10]);
+        {
+            let mut dbms =
+                dbs.try_mut_ranges(&[6..10, 0..3, 3..3, 3..5]).unwrap();
+            assert_eq!(dbms.len(), 4);
+            assert_eq!(dbms[0].len(), 4);
+            assert!(dbms[2].is_empty());
+            for (i, dbm) in dbms.iter_mut().enumerate() {
+                for x in dbm.iter_mut() {
+                    *x = i as u8 + 1;
+                }
+            }
+            // While the DivBufMuts are alive, no other accessor may be created
+            assert!(dbs.try_const().is_err());
+            assert!(dbs.try_mut().is_err());
+            drop(dbms.pop());
+            assert!(dbs.try_mut().is_err());
+        }
+        assert_eq!(
+            dbs.try_const().unwrap(),
+            [2, 2, 2, 4, 4, 0, 1, 1, 1, 1][..]
+        );
+        // Once they're all gone, the storage is reclaimable
+        let v: Vec<u8> = dbs.try_into().unwrap();
+        assert_eq!(v.len(), 10);
+    }
+
+    #[test]
+    pub fn try_mut_ranges_active() {
+        let dbs = DivBufShared::from(vec![0; 10]);
+        let _db = dbs.try_const().unwrap();
+        assert!(dbs.try_mut_ranges(&[0..2, 4..6]).is_err());
+    }
+
+    #[test]
+    pub fn try_mut_ranges_empty() {
+        let dbs = DivBufShared::from(vec![0; 10]);
+        assert!(dbs.try_mut_ranges(&[]).unwrap().is_empty());
+        assert!(dbs.try_mut().is_ok());
+    }
+
+    #[test]
+    pub fn try_mut_ranges_out_of_bounds() {
+        let dbs = DivBufShared::from(vec![0; 10]);
+        assert!(dbs.try_mut_ranges(&[0..2, 8..11]).is_err());
+        // The failed attempt must not leave anything locked
+        assert!(dbs.try_mut().is_ok());
+    }
+
+    #[test]
+    pub fn try_mut_ranges_overlap() {
+        let dbs = DivBufShared::from(vec![0; 10]);
+        assert!(dbs.try_mut_ranges(&[4..8, 0..5]).is_err());
+        assert!(dbs.try_mut_ranges(&[0..5, 0..5]).is_err());
+        assert!(dbs.try_mut().is_ok());
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    pub fn try_mut_ranges_reversed() {
+        let dbs = DivBufShared::from(vec![0; 10]);
+        assert!(dbs.try_mut_ranges(&[0..2, 5..4]).is_err());
+    }
+
+    #[cfg(feature = "experimental")]
+    #[test]
+    pub fn uninitialized() {
+        let cap = 4096;
+        let dbs = DivBufShared::uninitialized(cap);
+        assert_eq!(dbs.capacity(), cap);
+        assert_eq!(dbs.len(), cap);
+    }
+
+    #[test]
+    pub fn with_capacity_zeroed() {
+        let dbs = DivBufShared::with_capacity_zeroed(4096);
+        assert_eq!(dbs.len(), 4096);
+        assert!(dbs.capacity() >= 4096);
+        assert!(dbs.try_const().unwrap().iter().all(|&x| x == 0));
+    }
+
+    #[test]
+    pub fn with_read() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 250]);
+        let checksum = dbs
+            .with_read(|s| s.iter().fold(0u8, |acc, &x| acc.wrapping_add(x)));
+        assert_eq!(checksum.unwrap(), 4);
+        // The temporary DivBuf should be gone
+        assert!(dbs.try_mut().is_ok());
+    }
+
+    #[test]
+    pub fn with_read_while_writing() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let _dbm = dbs.try_mut().unwrap();
+        assert!(dbs.with_read(|s| s.len()).is_err());
+    }
+
+    #[test]
+    pub fn with_write() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let len = dbs.with_write(|dbm| {
+            for x in dbm.iter_mut() {
+                *x *= 2;
+            }
+            dbm.len()
+        });
+        assert_eq!(len.unwrap(), 5);
+        assert_eq!(dbs.try_const().unwrap(), [2, 4, 6, 8, 10][..]);
+    }
+
+    #[test]
+    pub fn with_write_while_reading() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let _db = dbs.try_const().unwrap();
+        assert!(dbs.with_write(|dbm| dbm.len()).is_err());
+    }
+
+    #[test]
+    pub fn to_vec() {
+        let v = vec![1, 2, 3, 4];
+        let dbs = DivBufShared::from(v);
+
+        {
+            let mut dbm = dbs.try_mut().unwrap();
+            assert_eq!(dbm, [1, 2, 3, 4][..]);
+            dbm[0] = 5;
+            assert_eq!(dbm, [5, 2, 3, 4][..]);
+        }
+
+        let v2: Vec<u8> = dbs.try_into().unwrap();
+        assert_eq!(v2, vec![5, 2, 3, 4]);
+    }
+
+    #[test]
+    pub fn to_vec_after_try_mut() {
+        let v = vec![1, 2, 3, 4];
+        let dbs = DivBufShared::from(v);
+
+        let _dbm = dbs.try_mut().unwrap();
+        let maybe_v: Result<Vec<u8>, _> = dbs.try_into();
+        assert!(maybe_v.is_err());
+    }
+
+    #[test]
+    pub fn to_vec_after_try_const() {
+        let v = vec![1, 2, 3, 4];
+        let dbs = DivBufShared::from(v);
+
+        let _db = dbs.try_const().unwrap();
+        let maybe_v: Result<Vec<u8>, _> = dbs.try_into();
+        assert!(maybe_v.is_err());
+    }
+
+    #[test]
+    pub fn to_vec_after_clone_inaccessible() {
+        let v = vec![1, 2, 3, 4];
+        let dbs = DivBufShared::from(v);
+
+        let dbm = dbs.try_mut().unwrap();
+        let _dbi = dbm.clone_inaccessible();
+        drop(dbm);
+
+        let maybe_v: Result<Vec<u8>, _> = dbs.try_into();
+        assert!(maybe_v.is_err());
+    }
+}
+
+//
+// DivBuf methods
+//
+mod divbuf_ {
+    use super::*;
+
+    #[test]
+    pub fn adjacent_to() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db0 = dbs.try_const().unwrap();
+        let mut db1 = db0.split_off(2);
+        let db2 = db1.split_off(2);
+        assert!(db0.adjacent_to(&db1));
+        assert!(db1.adjacent_to(&db2));
+        // Order matters
+        assert!(!db1.adjacent_to(&db0));
+        // Not adjacent
+        assert!(!db0.adjacent_to(&db2));
+        assert!(!db0.adjacent_to(&db0));
+        // unsplit should agree
+        assert!(db0.unsplit(db2).is_err());
+        assert!(db0.unsplit(db1).is_ok());
+    }
+
+    #[test]
+    pub fn adjacent_to_other_buffer() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let dbs1 = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db0 = dbs0.try_const().unwrap();
+        let _ = db0.split_off(2);
+        let db1 = dbs1.try_const().unwrap().slice_from(2);
+        assert!(!db0.adjacent_to(&db1));
+        // But a clone of the DivBufShared refers to the same buffer
+        let dbs2 = dbs0.clone();
+        let db2 = dbs2.try_const().unwrap().slice_from(2);
+        assert!(db0.adjacent_to(&db2));
+    }
+
+    #[test]
+    pub fn array_chunks() {
+        let dbs = DivBufShared::from((0..17).collect::<Vec<u8>>());
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db.array_chunks::<8>().count(), 2);
+        let mut iter = db.array_chunks::<8>();
+        assert_eq!(iter.next(), Some(&[0, 1, 2, 3, 4, 5, 6, 7]));
+        assert_eq!(iter.next(), Some(&[8, 9, 10, 11, 12, 13, 14, 15]));
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    pub fn array_chunks_short() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db.array_chunks::<8>().count(), 0);
+    }
+
+    #[test]
+    pub fn as_chunks() {
+        let dbs = DivBufShared::from((0..12).collect::<Vec<u8>>());
+        let db = dbs.try_const().unwrap().slice(1, 11);
+        let (chunks, rem) = db.as_chunks::<4>();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks, &[[1, 2, 3, 4], [5, 6, 7, 8]][..]);
+        assert_eq!(rem, &[9, 10][..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    pub fn as_chunks_zero() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let db = dbs.try_const().unwrap();
+        db.as_chunks::<0>();
+    }
+
+    #[test]
+    pub fn as_ref() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let db0 = dbs.try_const().unwrap();
+        let s: &[u8] = db0.as_ref();
+        assert_eq!(s, &[1, 2, 3]);
+    }
+
+    #[test]
+    pub fn as_ref_empty() {
+        let dbs = DivBufShared::from(vec![]);
+        let db0 = dbs.try_const().unwrap();
+        let s: &[u8] = db0.as_ref();
+        assert_eq!(s, &[]);
+    }
+
+    #[test]
+    pub fn bisect() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let db = dbs.try_const().unwrap();
+        for at in 0..=db.len() {
+            let (mut head, tail) = db.clone().bisect(at);
+            assert_eq!(head.len(), at);
+            assert_eq!(&head[..], &db[..at]);
+            assert_eq!(&tail[..], &db[at..]);
+            head.unsplit(tail).unwrap();
+            assert_eq!(head, db);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't split past the end")]
+    pub fn bisect_past_the_end() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let db = dbs.try_const().unwrap();
+        db.bisect(7);
+    }
+
+    #[test]
+    pub fn borrow() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let db0 = dbs.try_const().unwrap();
+        let s: &[u8] = db0.borrow();
+        assert_eq!(s, &[1, 2, 3]);
+    }
+
+    #[test]
+    pub fn borrow_split() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db0 = dbs.try_const().unwrap();
+        let db1 = db0.split_off(4);
+        let s: &[u8] = db1.borrow();
+        assert_eq!(s, &[5, 6]);
+    }
+
+    /// BTreeMap lookups by borrowed slice require that DivBuf's Ord agree
+    /// with the ordering of the slices it borrows as.
+    #[test]
+    pub fn btreemap_key() {
+        let dbs = DivBufShared::from(b"applebananacherrydate".to_vec());
+        let mut db = dbs.try_const().unwrap();
+        let mut map = BTreeMap::new();
+        // Insert out of order, so the map can't get lucky
+        let apple = db.split_to(5);
+        let banana = db.split_to(6);
+        let cherry = db.split_to(6);
+        map.insert(cherry, 2);
+        map.insert(db, 3);
+        map.insert(banana, 1);
+        map.insert(apple, 0);
+
+        assert_eq!(map.get(&b"apple"[..]), Some(&0));
+        assert_eq!(map.get(&b"date"[..]), Some(&3));
+        assert_eq!(map.get(&b"applebanana"[..]), None);
+        let r = map
+            .range::<[u8], _>((
+                Bound::Included(&b"b"[..]),
+                Bound::Excluded(&b"d"[..]),
+            ))
+            .map(|(k, v)| (k.to_vec(), *v))
+            .collect::<Vec<_>>();
+        assert_eq!(r, vec![(b"banana".to_vec(), 1), (b"cherry".to_vec(), 2)]);
+        let keys = map.keys().map(|k| k.to_vec()).collect::<Vec<_>>();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    pub fn hashmap_key() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db0 = dbs.try_const().unwrap();
+        let db1 = db0.split_off(4);
+        let mut map = HashMap::new();
+        map.insert(db0, "left");
+        map.insert(db1, "right");
+        assert_eq!(map.get(&[1, 2, 3, 4][..]), Some(&"left"));
+        assert_eq!(map.get(&[5, 6][..]), Some(&"right"));
+        assert_eq!(map.get(&[1, 2, 3, 4, 5, 6][..]), None);
+    }
+
+    #[test]
+    pub fn clone() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let db0 = dbs.try_const().unwrap();
+        let mut db1 = db0.clone();
+        assert_eq!(db0, db1);
+        // We should be able to modify one DivBuf without affecting the other
+        db1.split_off(1);
+        assert_ne!(db0, db1);
+    }
+
+    #[test]
+    pub fn clone_inaccessible() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let dbm = dbs.try_mut().unwrap();
+        let _dbi: DivBufInaccessible = dbm.clone_inaccessible();
+    }
+
+    #[test]
+    pub fn common_prefix_len() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let dbs1 = DivBufShared::from(vec![1, 2, 3, 9]);
+        let db0 = dbs0.try_const().unwrap();
+        let db1 = dbs1.try_const().unwrap();
+        assert_eq!(db0.common_prefix_len(&db1), 3);
+        assert_eq!(db1.common_prefix_len(&db0), 3);
+    }
+
+    #[test]
+    pub fn common_prefix_len_identical() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 1, 2, 3]);
+        let db = dbs.try_const().unwrap();
+        let db0 = db.slice(0, 3);
+        let db1 = db.slice(3, 6);
+        assert_eq!(db0.common_prefix_len(&db1), 3);
+        // One buffer is a prefix of the other
+        assert_eq!(db0.common_prefix_len(&db), 3);
+    }
+
+    #[test]
+    pub fn common_prefix_len_none() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db.slice(0, 2).common_prefix_len(&db.slice(2, 4)), 0);
+        assert_eq!(db.common_prefix_len(&db.slice(0, 0)), 0);
+    }
+
+    #[test]
+    pub fn count_ones() {
+        // Long enough to exercise both the word-at-a-time path and the
+        // remainder
+        let v = (0..=255u8).collect::<Vec<_>>();
+        let expected: u64 = v.iter().map(|b| u64::from(b.count_ones())).sum();
+        let dbs = DivBufShared::from(v);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db.count_ones(), expected);
+        assert_eq!(db.count_zeros(), 256 * 8 - expected);
+        let db = db.slice(1, 12);
+        assert_eq!(db.count_ones(), 20);
+        assert_eq!(db.count_zeros(), 68);
+    }
+
+    #[test]
+    pub fn count_ones_empty() {
+        let dbs = DivBufShared::from(vec![]);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db.count_ones(), 0);
+        assert_eq!(db.count_zeros(), 0);
+    }
+
+    #[test]
+    pub fn deref() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let db = dbs.try_const().unwrap();
+        let slice: &[u8] = &db;
+        assert_eq!(slice, &[1, 2, 3]);
+    }
+
+    #[test]
+    pub fn deref_empty() {
+        let dbs = DivBufShared::from(vec![]);
+        let db = dbs.try_const().unwrap();
+        let slice: &[u8] = &db;
+        assert_eq!(slice, &[]);
+    }
+
+    // A DivBuf should be able to own its storage, and will free it on last drop
+    #[test]
+    pub fn drop_last() {
         let dbs0 = DivBufShared::from(vec![1, 2, 3]);
         let _db0 = dbs0.try_const().unwrap();
         drop(dbs0);
     }
 
+    #[test]
+    pub fn debug() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db = dbs.try_const().unwrap();
+        let _ = db.split_to(1);
+        let _ = db.split_off(4);
+        assert_eq!(
+            format!("{:?}", db),
+            "DivBuf { len: 4, data: [2, 3, 4, 5] }"
+        );
+    }
+
+    #[test]
+    pub fn debug_long() {
+        let dbs = DivBufShared::from(vec![0; 33]);
+        let db = dbs.try_const().unwrap();
+        let expected =
+            format!("DivBuf {{ len: 33, data: [{}...] }}", "0, ".repeat(32));
+        assert_eq!(format!("{:?}", db), expected);
+    }
+
     #[test]
     pub fn eq() {
         let dbs0 = DivBufShared::from(vec![1, 2, 3]);
@@ -423,6 +1738,22 @@ mod divbuf_ {
         assert_ne!(db0, db2);
     }
 
+    #[test]
+    pub fn eq_array() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let mut db0 = dbs.try_const().unwrap();
+        let db1 = db0.split_off(3);
+        assert_eq!(db0, [1, 2, 3]);
+        assert_eq!([1, 2, 3], db0);
+        assert_eq!(db1, [4]);
+        assert_eq!([4], db1);
+        assert_ne!(db0, [1, 2]);
+        assert_ne!([1, 2, 3, 4], db0);
+        assert_ne!(db0, [1, 2, 4]);
+        assert_ne!(db1, []);
+        assert_ne!([], db1);
+    }
+
     #[test]
     pub fn from_divbufmut() {
         let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
@@ -430,6 +1761,87 @@ mod divbuf_ {
         let _db = DivBuf::from(dbm);
     }
 
+    #[test]
+    pub fn into_vec() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let db = dbs.try_const().unwrap();
+        let ptr = db.as_ptr();
+        drop(dbs);
+        let v = db.into_vec().unwrap();
+        assert_eq!(v, vec![1, 2, 3, 4, 5, 6]);
+        // The storage should not have been copied
+        assert_eq!(v.as_ptr(), ptr);
+    }
+
+    #[test]
+    pub fn into_vec_inaccessible() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let db = dbs.try_const().unwrap();
+        let _dbi = db.clone_inaccessible();
+        drop(dbs);
+        let db = db.into_vec().unwrap_err();
+        assert_eq!(db, [1, 2, 3, 4, 5, 6][..]);
+    }
+
+    #[test]
+    pub fn into_vec_other_divbuf() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let db0 = dbs.try_const().unwrap();
+        let db1 = db0.clone();
+        drop(dbs);
+        let db0 = db0.into_vec().unwrap_err();
+        drop(db1);
+        assert!(db0.into_vec().is_ok());
+    }
+
+    #[test]
+    pub fn into_vec_partial() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db0 = dbs.try_const().unwrap();
+        drop(db0.split_off(4));
+        drop(dbs);
+        let db0 = db0.into_vec().unwrap_err();
+        assert_eq!(db0, [1, 2, 3, 4][..]);
+    }
+
+    #[test]
+    pub fn into_vec_shared() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let db = dbs.try_const().unwrap();
+        let db = db.into_vec().unwrap_err();
+        assert_eq!(db, [1, 2, 3, 4, 5, 6][..]);
+    }
+
+    #[test]
+    pub fn is_all() {
+        let dbs = DivBufShared::from(vec![0xff; 21]);
+        let db = dbs.try_const().unwrap();
+        assert!(db.is_all(0xff));
+        assert!(!db.is_all(0));
+    }
+
+    #[test]
+    pub fn is_all_empty() {
+        let dbs = DivBufShared::from(vec![]);
+        let db = dbs.try_const().unwrap();
+        assert!(db.is_all(0));
+        assert!(db.is_all(0xff));
+    }
+
+    #[test]
+    pub fn is_all_mixed() {
+        let mut v = vec![0; 21];
+        v[0] = 1;
+        v[20] = 2;
+        let dbs = DivBufShared::from(v);
+        let db = dbs.try_const().unwrap();
+        assert!(!db.is_all(0));
+        assert!(db.slice(1, 20).is_all(0));
+        // Differences in the word-at-a-time part and in the remainder
+        assert!(!db.slice(0, 20).is_all(0));
+        assert!(!db.slice(1, 21).is_all(0));
+    }
+
     #[test]
     pub fn is_empty() {
         let dbs0 = DivBufShared::with_capacity(64);
@@ -441,6 +1853,56 @@ mod divbuf_ {
         assert!(!db1.is_empty());
     }
 
+    #[test]
+    pub fn is_terminal() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db0 = dbs.try_const().unwrap();
+        assert!(db0.is_terminal());
+        let db1 = db0.split_off(4);
+        assert!(!db0.is_terminal());
+        assert!(db1.is_terminal());
+        assert!(!db1.slice_to(1).is_terminal());
+        // An empty buffer at the end is still terminal
+        assert!(db1.slice_from(2).is_terminal());
+    }
+
+    /// A frozen terminal DivBufMut can be thawed and extended again
+    #[test]
+    pub fn is_terminal_after_freeze() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let dbm1 = dbm0.split_off(4);
+        let db0 = dbm0.freeze();
+        let db1 = dbm1.freeze();
+        assert!(!db0.is_terminal());
+        assert!(db1.is_terminal());
+        drop(db0);
+        let mut dbm1 = db1.try_mut().unwrap();
+        assert!(dbm1.try_extend(&[7]).is_ok());
+    }
+
+    #[test]
+    pub fn iter() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db0 = dbs.try_const().unwrap();
+        let db1 = db0.split_off(4);
+        assert_eq!(db0.iter().map(|&x| u32::from(x)).sum::<u32>(), 10);
+        assert_eq!(db1.iter().map(|&x| u32::from(x)).sum::<u32>(), 11);
+    }
+
+    #[test]
+    pub fn eq_ignore_ascii_case() {
+        let dbs = DivBufShared::from(&b"Content-Length: 42"[..]);
+        let db = dbs.try_const().unwrap();
+        let name = db.slice_to(14);
+        assert!(name.eq_ignore_ascii_case(b"Content-Length"));
+        assert!(name.eq_ignore_ascii_case(b"CONTENT-LENGTH"));
+        assert!(name.eq_ignore_ascii_case(b"content-length"));
+        assert!(!name.eq_ignore_ascii_case(b"Content-Type"));
+        assert!(!name.eq_ignore_ascii_case(b"Content-Length:"));
+        assert!(!db.eq_ignore_ascii_case(b"content-length"));
+    }
+
     #[test]
     pub fn hash() {
         let v = vec![1, 2, 3, 4, 5, 6];
@@ -450,6 +1912,29 @@ mod divbuf_ {
         assert_eq!(simple_hash(&db0), expected);
     }
 
+    #[test]
+    // Miri would rightly complain about the leak
+    #[cfg_attr(miri, ignore)]
+    pub fn leak() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let s = dbs.try_const().unwrap().slice(1, 4).leak();
+        assert_eq!(s, &[2, 3, 4][..]);
+        // The leaked slice holds a read lock forever
+        assert!(dbs.try_mut().is_err());
+        drop(dbs);
+        assert_eq!(s, &[2, 3, 4][..]);
+    }
+
+    #[test]
+    pub fn map_collect() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 255]);
+        let db = dbs.try_const().unwrap().slice_from(1);
+        let v = db.map_collect(|x| x.wrapping_add(1));
+        assert_eq!(v, vec![3, 4, 5, 0]);
+        assert_eq!(db, [2, 3, 4, 255][..]);
+        assert_eq!(dbs.try_const().unwrap(), [1, 2, 3, 4, 255][..]);
+    }
+
     #[test]
     pub fn ord() {
         let dbs = DivBufShared::from(vec![0, 1, 0, 2]);
@@ -466,6 +1951,78 @@ mod divbuf_ {
         assert!(db0 < db1);
     }
 
+    #[test]
+    pub fn position() {
+        let dbs = DivBufShared::from(&b",a,b,"[..]);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db.position(b','), Some(0));
+        assert_eq!(db.rposition(b','), Some(4));
+        assert_eq!(db.position(b'b'), Some(3));
+        assert_eq!(db.rposition(b'a'), Some(1));
+        assert_eq!(db.position(b';'), None);
+        assert_eq!(db.rposition(b';'), None);
+    }
+
+    /// Positions are relative to the window, not the underlying storage
+    #[test]
+    pub fn position_sliced() {
+        let dbs = DivBufShared::from(&b",a,b,"[..]);
+        let db = dbs.try_const().unwrap().slice(1, 4);
+        assert_eq!(db.position(b','), Some(1));
+        assert_eq!(db.rposition(b','), Some(1));
+        assert_eq!(db.position(b'b'), Some(2));
+        assert_eq!(db.slice(0, 1).position(b','), None);
+        assert_eq!(db.slice(0, 0).rposition(b','), None);
+    }
+
+    #[test]
+    pub fn read_array() {
+        let dbs = DivBufShared::from((0..16).collect::<Vec<u8>>());
+        let db = dbs.try_const().unwrap().slice_from(2);
+        assert_eq!(db.read_array::<0>(14), Some([]));
+        assert_eq!(db.read_array::<1>(0), Some([2]));
+        assert_eq!(db.read_array::<2>(12), Some([14, 15]));
+        assert_eq!(db.read_array::<4>(3), Some([5, 6, 7, 8]));
+        assert_eq!(db.read_array::<8>(6), Some([8, 9, 10, 11, 12, 13, 14, 15]));
+    }
+
+    #[test]
+    pub fn read_array_out_of_range() {
+        let dbs = DivBufShared::from((0..16).collect::<Vec<u8>>());
+        let db = dbs.try_const().unwrap().slice_to(8);
+        assert_eq!(db.read_array::<0>(9), None);
+        assert_eq!(db.read_array::<1>(8), None);
+        assert_eq!(db.read_array::<4>(5), None);
+        assert_eq!(db.read_array::<16>(0), None);
+        assert_eq!(db.read_array::<2>(usize::MAX), None);
+    }
+
+    #[test]
+    pub fn read_frame_u32_be() {
+        let dbs = DivBufShared::from(vec![0, 0, 0, 3, 1, 2, 3, 0, 0, 0, 0, 4]);
+        let mut db = dbs.try_const().unwrap();
+        assert_eq!(db.read_frame_u32_be().unwrap(), [1, 2, 3][..]);
+        assert_eq!(db, [0, 0, 0, 0, 4][..]);
+        assert!(db.read_frame_u32_be().unwrap().is_empty());
+        assert_eq!(db, [4][..]);
+    }
+
+    #[test]
+    pub fn read_frame_u32_be_short_body() {
+        let dbs = DivBufShared::from(vec![0, 0, 1, 0, 1, 2, 3]);
+        let mut db = dbs.try_const().unwrap();
+        assert!(db.read_frame_u32_be().is_none());
+        assert_eq!(db, [0, 0, 1, 0, 1, 2, 3][..]);
+    }
+
+    #[test]
+    pub fn read_frame_u32_be_short_prefix() {
+        let dbs = DivBufShared::from(vec![0, 0, 0]);
+        let mut db = dbs.try_const().unwrap();
+        assert!(db.read_frame_u32_be().is_none());
+        assert_eq!(db, [0, 0, 0][..]);
+    }
+
     #[test]
     pub fn send() {
         let dbs = DivBufShared::with_capacity(4096);
@@ -477,6 +2034,18 @@ mod divbuf_ {
         .unwrap();
     }
 
+    #[test]
+    pub fn shared_len() {
+        let mut v = Vec::with_capacity(64);
+        v.extend_from_slice(&[1, 2, 3, 4, 5, 6]);
+        let dbs = DivBufShared::from(v);
+        let db = dbs.try_const().unwrap().slice(2, 5);
+        assert_eq!(db.len(), 3);
+        assert_eq!(db.shared_len(), 6);
+        assert!(db.shared_capacity() >= 64);
+        assert_eq!(db.shared_capacity(), dbs.capacity());
+    }
+
     #[test]
     pub fn sync() {
         lazy_static! {
@@ -535,6 +2104,36 @@ mod divbuf_ {
         assert_eq!(db0.slice_to(3), [1, 2, 3][..]);
     }
 
+    #[test]
+    pub fn split_first() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let mut db0 = dbs.try_const().unwrap();
+        let db1 = db0.split_off(1);
+        let db2 = db0.clone().split_to(0);
+        assert!(db2.split_first().is_none());
+        let (first, rest) = db0.split_first().unwrap();
+        assert_eq!(first, 1);
+        assert!(rest.is_empty());
+        let (first, rest) = db1.split_first().unwrap();
+        assert_eq!(first, 2);
+        assert_eq!(rest, [3][..]);
+    }
+
+    #[test]
+    pub fn split_last() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let mut db0 = dbs.try_const().unwrap();
+        let db1 = db0.split_off(1);
+        let db2 = db0.clone().split_to(0);
+        assert!(db2.split_last().is_none());
+        let (last, rest) = db0.split_last().unwrap();
+        assert_eq!(last, 1);
+        assert!(rest.is_empty());
+        let (last, rest) = db1.split_last().unwrap();
+        assert_eq!(last, 3);
+        assert_eq!(rest, [2][..]);
+    }
+
     #[test]
     pub fn split_off() {
         let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
@@ -555,6 +2154,46 @@ mod divbuf_ {
         assert_eq!(db_end, [][..]);
     }
 
+    #[test]
+    pub fn split_off_aligned() {
+        let dbs = DivBufShared::from((0..32).collect::<Vec<u8>>());
+        let mut db0 = dbs.try_const().unwrap().slice(3, 30);
+        let db1 = db0.split_off_aligned(8);
+        assert_eq!(ByteWindow::offset(&db1), 24);
+        assert_eq!(db1, [24, 25, 26, 27, 28, 29][..]);
+        assert_eq!(db0.len(), 21);
+        assert_eq!(db0[0], 3);
+        let db2 = db0.split_off_aligned(16);
+        assert_eq!(ByteWindow::offset(&db2), 16);
+        assert_eq!(db0.len(), 13);
+    }
+
+    #[test]
+    pub fn split_off_aligned_end() {
+        let dbs = DivBufShared::from((0..32).collect::<Vec<u8>>());
+        let mut db0 = dbs.try_const().unwrap().slice_from(3);
+        let db1 = db0.split_off_aligned(8);
+        assert!(db1.is_empty());
+        assert_eq!(db0.len(), 29);
+    }
+
+    #[test]
+    pub fn split_off_aligned_no_boundary() {
+        let dbs = DivBufShared::from((0..32).collect::<Vec<u8>>());
+        let mut db0 = dbs.try_const().unwrap().slice(9, 15);
+        let db1 = db0.split_off_aligned(8);
+        assert!(db1.is_empty());
+        assert_eq!(db0, [9, 10, 11, 12, 13, 14][..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "align must be a power of two")]
+    pub fn split_off_aligned_not_power_of_two() {
+        let dbs = DivBufShared::from((0..32).collect::<Vec<u8>>());
+        let mut db0 = dbs.try_const().unwrap();
+        db0.split_off_aligned(6);
+    }
+
     #[test]
     #[should_panic(expected = "Can't split past the end")]
     pub fn split_off_past_the_end() {
@@ -591,6 +2230,213 @@ mod divbuf_ {
         db0.split_to(7);
     }
 
+    #[test]
+    pub fn stride() {
+        let dbs = DivBufShared::from((0..10).collect::<Vec<u8>>());
+        let db = dbs.try_const().unwrap().slice_from(1);
+        assert_eq!(db.stride(0, 3), vec![1, 4, 7]);
+        assert_eq!(db.stride(1, 3), vec![2, 5, 8]);
+        assert_eq!(db.stride(2, 3), vec![3, 6, 9]);
+        assert_eq!(db.stride(0, 1), db.to_vec());
+        assert_eq!(db.stride(4, 100), vec![5]);
+    }
+
+    #[test]
+    pub fn stride_past_the_end() {
+        let dbs = DivBufShared::from((0..10).collect::<Vec<u8>>());
+        let db = dbs.try_const().unwrap().slice_to(5);
+        assert_eq!(db.stride(4, 2), vec![4]);
+        assert!(db.stride(5, 2).is_empty());
+        assert!(db.stride(6, 2).is_empty());
+        assert!(db.stride(usize::MAX, 1).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be non-zero")]
+    pub fn stride_zero() {
+        let dbs = DivBufShared::from((0..10).collect::<Vec<u8>>());
+        let db = dbs.try_const().unwrap();
+        db.stride(0, 0);
+    }
+
+    #[test]
+    pub fn strip_prefix() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let mut db = dbs.try_const().unwrap();
+        assert!(db.strip_prefix(&[1, 2]));
+        assert_eq!(db, [3, 4, 5][..]);
+        assert!(db.strip_prefix(&[]));
+        assert_eq!(db, [3, 4, 5][..]);
+        assert!(db.strip_prefix(&[3, 4, 5]));
+        assert!(db.is_empty());
+    }
+
+    #[test]
+    pub fn strip_prefix_mismatch() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let mut db = dbs.try_const().unwrap();
+        assert!(!db.strip_prefix(&[1, 3]));
+        assert_eq!(db, [1, 2, 3, 4, 5][..]);
+    }
+
+    #[test]
+    pub fn strip_prefix_too_long() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let mut db = dbs.try_const().unwrap().slice(0, 2);
+        assert!(!db.strip_prefix(&[1, 2, 3]));
+        assert_eq!(db, [1, 2][..]);
+    }
+
+    #[test]
+    pub fn take() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db0 = dbs.try_const().unwrap();
+        let dbs_ref = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db_ref = dbs_ref.try_const().unwrap();
+        for &n in &[2, 0, 4] {
+            assert_eq!(db0.take(n), db_ref.split_to(n));
+            assert_eq!(db0, db_ref);
+        }
+        assert!(db0.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't split past the end")]
+    pub fn take_past_the_end() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db0 = dbs.try_const().unwrap();
+        db0.take(7);
+    }
+
+    #[test]
+    pub fn to_arc_slice() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let arc = {
+            let mut db = dbs.try_const().unwrap();
+            db.split_off(3).to_arc_slice()
+        };
+        // The copy should not keep the DivBufShared locked
+        let mut dbm = dbs.try_mut().unwrap();
+        dbm[3] = 9;
+        drop(dbm);
+        assert_eq!(&arc[..], &[4][..]);
+        drop(dbs);
+        assert_eq!(&arc[..], &[4][..]);
+    }
+
+    #[test]
+    pub fn to_boxed_slice() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let boxed = {
+            let mut db = dbs.try_const().unwrap();
+            db.split_to(3).to_boxed_slice()
+        };
+        let mut dbm = dbs.try_mut().unwrap();
+        dbm[0] = 9;
+        drop(dbm);
+        assert_eq!(&boxed[..], &[1, 2, 3][..]);
+        drop(dbs);
+        assert_eq!(&boxed[..], &[1, 2, 3][..]);
+    }
+
+    #[test]
+    pub fn trim_ascii() {
+        let dbs = DivBufShared::from(&b" \t hello world\r\n"[..]);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db.trim_ascii(), b"hello world"[..]);
+        assert_eq!(db.trim_ascii_start(), b"hello world\r\n"[..]);
+        assert_eq!(db.trim_ascii_end(), b" \t hello world"[..]);
+    }
+
+    #[test]
+    pub fn trim_ascii_all_whitespace() {
+        let dbs = DivBufShared::from(&b" \t\r\n\x0c"[..]);
+        let db = dbs.try_const().unwrap();
+        assert!(db.trim_ascii().is_empty());
+        assert!(db.trim_ascii_start().is_empty());
+        assert!(db.trim_ascii_end().is_empty());
+    }
+
+    #[test]
+    pub fn trim_ascii_end() {
+        let dbs = DivBufShared::from(&b"hello \n"[..]);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db.trim_ascii(), b"hello"[..]);
+        assert_eq!(db.trim_ascii_start(), b"hello \n"[..]);
+        assert_eq!(db.trim_ascii_end(), b"hello"[..]);
+    }
+
+    #[test]
+    pub fn trim_ascii_start() {
+        let dbs = DivBufShared::from(&b"\t hello"[..]);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db.trim_ascii(), b"hello"[..]);
+        assert_eq!(db.trim_ascii_start(), b"hello"[..]);
+        assert_eq!(db.trim_ascii_end(), b"\t hello"[..]);
+    }
+
+    #[test]
+    pub fn truncate_to() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db = dbs.try_const().unwrap();
+        db.truncate_to(6);
+        assert_eq!(db, [1, 2, 3, 4, 5, 6][..]);
+        db.truncate_to(4);
+        assert_eq!(db, [1, 2, 3, 4][..]);
+        db.truncate_to(0);
+        assert!(db.is_empty());
+        // No other reader may be left behind
+        let dbm = db.try_mut().unwrap();
+        drop(dbm);
+        let v: Vec<u8> = dbs.try_into().unwrap();
+        assert_eq!(v, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't split past the end")]
+    pub fn truncate_to_past_the_end() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db = dbs.try_const().unwrap();
+        db.truncate_to(7);
+    }
+
+    #[test]
+    pub fn try_into_string() {
+        let dbs = DivBufShared::from(&b"Hello, world"[..]);
+        let db = dbs.try_const().unwrap();
+        assert_eq!(String::try_from(&db.slice_to(5)).unwrap(), "Hello");
+        assert_eq!(String::try_from(db.slice_from(7)).unwrap(), "world");
+    }
+
+    #[test]
+    pub fn try_into_string_invalid() {
+        let dbs = DivBufShared::from(vec![b'a', 0xff, b'b']);
+        let db = dbs.try_const().unwrap();
+        assert!(String::try_from(&db).is_err());
+        let e = String::try_from(db).unwrap_err();
+        assert_eq!(e.utf8_error().valid_up_to(), 1);
+        assert_eq!(e.into_bytes(), vec![b'a', 0xff, b'b']);
+    }
+
+    #[test]
+    pub fn try_into_chunks() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let db = dbs.try_const().unwrap();
+        let mut chunks = db.try_into_chunks(3).unwrap();
+        assert_eq!(chunks.next().unwrap(), [1, 2, 3][..]);
+        assert_eq!(chunks.next().unwrap(), [4, 5][..]);
+        assert!(chunks.next().is_none());
+    }
+
+    #[test]
+    pub fn try_into_chunks_zero() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let db = dbs.try_const().unwrap().slice(1, 3);
+        let (db, e) = db.try_into_chunks(0).unwrap_err();
+        assert_eq!(db, [2, 3][..]);
+        assert_eq!(e.to_string(), "Chunk size must be non-zero");
+    }
+
     #[test]
     pub fn try_mut() {
         let dbs = DivBufShared::with_capacity(64);
@@ -659,6 +2505,18 @@ mod divbuf_inaccessible {
 
     // A DivBufInaccessible should be able to own its storage, and will free it
     // on last drop.
+    #[test]
+    pub fn debug() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let mut db = dbs.try_const().unwrap();
+        let _ = db.split_to(1);
+        let dbi = db.clone_inaccessible();
+        assert_eq!(
+            format!("{:?}", dbi),
+            "DivBufInaccessible { offset: 1, len: 3 }"
+        );
+    }
+
     #[test]
     pub fn drop_last() {
         let dbs0 = DivBufShared::from(vec![1, 2, 3]);
@@ -668,6 +2526,56 @@ mod divbuf_inaccessible {
         drop(dbs0);
     }
 
+    #[test]
+    pub fn eq() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let db = dbs.try_const().unwrap();
+        let dbi0 = db.clone_inaccessible();
+        let dbi1 = dbi0.clone();
+        assert_eq!(dbi0, dbi1);
+        assert_eq!(simple_hash(&dbi0), simple_hash(&dbi1));
+    }
+
+    #[test]
+    pub fn eq_different_buffers() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3, 4]);
+        let dbs1 = DivBufShared::from(vec![1, 2, 3, 4]);
+        let dbi0 = dbs0.try_const().unwrap().clone_inaccessible();
+        let dbi1 = dbs1.try_const().unwrap().clone_inaccessible();
+        // Identical contents, but not the same buffer
+        assert_ne!(dbi0, dbi1);
+    }
+
+    #[test]
+    pub fn eq_different_windows() {
+        let dbs = DivBufShared::from(vec![1, 2, 1, 2]);
+        let db = dbs.try_const().unwrap();
+        let dbi0 = db.slice(0, 2).clone_inaccessible();
+        let dbi1 = db.slice(2, 4).clone_inaccessible();
+        let dbi2 = db.slice(0, 3).clone_inaccessible();
+        assert_ne!(dbi0, dbi1);
+        assert_ne!(dbi0, dbi2);
+        let set: HashSet<_> =
+            vec![dbi0.clone(), dbi1, dbi2, dbi0].into_iter().collect();
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    pub fn is_valid() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db = dbs.try_const().unwrap();
+        let dbi0 = db.split_off(2).clone_inaccessible();
+        let dbi1 = db.clone_inaccessible();
+        drop(db);
+        assert!(dbi0.is_valid());
+        assert!(dbi1.is_valid());
+        dbs.try_mut().unwrap().try_truncate(3).unwrap();
+        assert!(!dbi0.is_valid());
+        assert!(dbi1.is_valid());
+        dbs.try_mut().unwrap().try_extend(b"xyz").unwrap();
+        assert!(dbi0.is_valid());
+    }
+
     #[test]
     pub fn send() {
         let dbs = DivBufShared::with_capacity(4096);
@@ -712,6 +2620,32 @@ mod divbuf_inaccessible {
         dbi.try_const().unwrap();
     }
 
+    /// Upgrading a window that was truncated away must fail cleanly
+    #[test]
+    pub fn try_const_truncated() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let dbi = dbs.try_const().unwrap().slice(2, 5).clone_inaccessible();
+        dbs.try_mut().unwrap().try_truncate(4).unwrap();
+        assert!(dbi.try_const().is_err());
+        // The failed attempt should not leave a reader behind
+        assert!(dbs.try_mut().is_ok());
+    }
+
+    /// A window that was only partially truncated is rejected, not clamped
+    #[test]
+    pub fn try_const_truncated_partially() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let dbi = dbs.try_const().unwrap().slice_from(2).clone_inaccessible();
+        dbs.try_mut().unwrap().try_truncate(4).unwrap();
+        let e = dbi.try_const().unwrap_err();
+        assert_eq!(e.to_string(), "The buffer was truncated");
+        let e = dbi.try_mut().unwrap_err();
+        assert_eq!(e.to_string(), "The buffer was truncated");
+        // Once the buffer grows back, the window is usable again
+        dbs.try_mut().unwrap().try_extend(&[7, 8]).unwrap();
+        assert_eq!(dbi.try_const().unwrap(), [3, 4, 7, 8][..]);
+    }
+
     #[test]
     pub fn try_mut_failure() {
         let dbs0 = DivBufShared::from(vec![1, 2, 3]);
@@ -728,6 +2662,50 @@ mod divbuf_inaccessible {
         drop(db0);
         dbi.try_mut().unwrap();
     }
+
+    /// Append to a buffer via a DivBufInaccessible, after all readers are gone
+    #[test]
+    pub fn try_mut_terminal() {
+        let dbs = DivBufShared::with_capacity(64);
+        let dbi = {
+            let mut dbm = dbs.try_mut().unwrap();
+            dbm.try_extend(b"one").unwrap();
+            dbm.clone_inaccessible()
+        };
+        let db0 = dbi.try_const().unwrap();
+        let db1 = db0.clone();
+        assert!(dbi.try_mut_terminal().is_err());
+        drop(db0);
+        assert!(dbi.try_mut_terminal().is_err());
+        drop(db1);
+        let mut dbm = dbi.try_mut_terminal().unwrap();
+        dbm.try_extend(b" two").unwrap();
+        drop(dbm);
+        assert_eq!(dbs.try_const().unwrap(), b"one two"[..]);
+    }
+
+    /// A window that no longer reaches the end of the buffer can't be extended
+    #[test]
+    pub fn try_mut_terminal_nonterminal() {
+        let dbs = DivBufShared::with_capacity(64);
+        let dbi = dbs.try_mut().unwrap().clone_inaccessible();
+        dbs.try_mut().unwrap().try_extend(b"one").unwrap();
+        assert!(dbi.try_mut_terminal().is_err());
+        // The failed attempt should not leave a writer behind
+        assert!(dbs.try_const().is_ok());
+    }
+
+    /// Upgrading a window that was truncated away must fail cleanly
+    #[test]
+    pub fn try_mut_truncated() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let dbi = dbs.try_mut().unwrap().split_off(4).clone_inaccessible();
+        dbs.reset().unwrap();
+        assert!(dbi.try_mut().is_err());
+        assert!(dbi.try_mut_terminal().is_err());
+        // The failed attempts should not leave a writer behind
+        assert!(dbs.try_const().is_ok());
+    }
 }
 
 //
@@ -736,6 +2714,25 @@ mod divbuf_inaccessible {
 mod divbuf_mut {
     use super::*;
 
+    #[test]
+    pub fn as_chunks_mut() {
+        let dbs = DivBufShared::from((0..12).collect::<Vec<u8>>());
+        {
+            let mut dbm = dbs.try_mut().unwrap();
+            let mut dbm = dbm.split_off(1);
+            let _tail = dbm.split_off(10);
+            let (chunks, rem) = dbm.as_chunks_mut::<4>();
+            assert_eq!(chunks.len(), 2);
+            assert_eq!(rem.len(), 2);
+            for chunk in chunks.iter_mut() {
+                chunk.reverse();
+            }
+            rem[0] = 0;
+        }
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db, [0, 4, 3, 2, 1, 8, 7, 6, 5, 0, 10, 11][..]);
+    }
+
     #[test]
     pub fn as_ref() {
         let dbs = DivBufShared::from(vec![1, 2, 3]);
@@ -760,6 +2757,29 @@ mod divbuf_mut {
         assert_eq!(s, &[1, 2, 3]);
     }
 
+    #[test]
+    pub fn borrow_split() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let dbm1 = dbm0.split_off(4);
+        let s: &[u8] = dbm1.borrow();
+        assert_eq!(s, &[5, 6]);
+    }
+
+    #[test]
+    pub fn borrowmut_split() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            let mut dbm1 = dbm0.split_off(4);
+            let s: &mut [u8] = dbm1.borrow_mut();
+            assert_eq!(s.len(), 2);
+            s[0] = 9;
+        }
+        let db0 = dbs.try_const().unwrap();
+        assert_eq!(db0, [1, 2, 3, 4, 9, 6][..]);
+    }
+
     #[test]
     pub fn borrowmut() {
         let dbs = DivBufShared::from(vec![1, 2, 3]);
@@ -780,6 +2800,24 @@ mod divbuf_mut {
         let _dbi: DivBufInaccessible = db.clone_inaccessible();
     }
 
+    #[test]
+    pub fn clone_to_shared() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let mut dbm = dbs.try_mut().unwrap();
+        let _ = dbm.split_to(1);
+        let copy = dbm.clone_to_shared();
+        {
+            let mut copy_dbm = copy.try_mut().unwrap();
+            assert_eq!(copy_dbm, [2, 3, 4][..]);
+            copy_dbm[0] = 9;
+            copy_dbm.extend([5].iter());
+        }
+        assert_eq!(dbm, [2, 3, 4][..]);
+        drop(dbm);
+        assert_eq!(dbs.try_const().unwrap(), [1, 2, 3, 4][..]);
+        assert_eq!(copy.try_const().unwrap(), [9, 3, 4, 5][..]);
+    }
+
     #[test]
     pub fn deref() {
         let dbs = DivBufShared::from(vec![1, 2, 3]);
@@ -806,6 +2844,24 @@ mod divbuf_mut {
         assert_eq!(slice, &[9, 2, 3]);
     }
 
+    // Writing through slices of two different DivBufMuts at the same time
+    // must not be considered aliasing.
+    #[test]
+    pub fn derefmut_simultaneous() {
+        let dbs = DivBufShared::from(vec![0; 4]);
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            let mut dbm1 = dbm0.split_off(2);
+            let s0: &mut [u8] = &mut dbm0;
+            let s1: &mut [u8] = &mut dbm1;
+            s0[0] = 1;
+            s1[0] = 2;
+            s0[1] = 3;
+            s1[1] = 4;
+        }
+        assert_eq!(dbs.try_const().unwrap(), [1, 3, 2, 4][..]);
+    }
+
     #[test]
     pub fn derefmut_empty() {
         let dbs = DivBufShared::from(vec![]);
@@ -814,46 +2870,171 @@ mod divbuf_mut {
         assert_eq!(slice, &[]);
     }
 
-    // A DivBufMut should be able to own its storage, and will free it on last drop
+    // A DivBufMut should be able to own its storage, and will free it on last drop
+    #[test]
+    pub fn debug() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let mut dbm = dbs.try_mut().unwrap();
+        let _ = dbm.split_off(2);
+        assert_eq!(format!("{:?}", dbm), "DivBufMut { len: 2, data: [1, 2] }");
+    }
+
+    #[test]
+    pub fn drop_last() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3]);
+        let _dbm0 = dbs0.try_mut().unwrap();
+        drop(dbs0);
+    }
+
+    #[test]
+    pub fn eq() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3]);
+        let dbs1 = DivBufShared::from(vec![1, 2, 3]);
+        let dbs2 = DivBufShared::from(vec![1, 2]);
+        let dbm0 = dbs0.try_mut().unwrap();
+        let dbm1 = dbs1.try_mut().unwrap();
+        let dbm2 = dbs2.try_mut().unwrap();
+        assert_eq!(dbm0, dbm1);
+        assert_ne!(dbm0, dbm2);
+    }
+
+    #[test]
+    pub fn eq_array() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let dbm1 = dbm0.split_off(3);
+        assert_eq!(dbm0, [1, 2, 3]);
+        assert_eq!([1, 2, 3], dbm0);
+        assert_eq!(dbm1, [4]);
+        assert_eq!([4], dbm1);
+        assert_ne!(dbm0, [1, 2]);
+        assert_ne!([1, 2, 3, 4], dbm0);
+        assert_ne!(dbm0, [1, 2, 4]);
+        assert_ne!(dbm1, []);
+        assert_ne!([], dbm1);
+    }
+
+    #[test]
+    pub fn extend() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        {
+            let mut dbm = dbs.try_mut().unwrap();
+            dbm.extend([4, 5, 6].iter());
+        }
+        // verify that dbs.inner.vec was extended
+        let db = dbs.try_const().unwrap();
+        let slice: &[u8] = &db;
+        assert_eq!(slice, &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "extend into the middle of a buffer")]
+    pub fn extend_from_the_middle() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm = dbs.try_mut().unwrap();
+        let mut dbm_begin = dbm.split_to(3);
+        dbm_begin.extend([7, 8, 9].iter());
+    }
+
+    #[test]
+    #[should_panic(expected = "extend into the middle of a buffer")]
+    pub fn extend_owned_from_the_middle() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm = dbs.try_mut().unwrap();
+        let mut dbm_begin = dbm.split_to(3);
+        dbm_begin.extend(vec![7, 8, 9]);
+    }
+
+    #[test]
+    pub fn extend_owned() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let mut dbm = dbs.try_mut().unwrap();
+        dbm.extend(vec![4, 5]);
+        dbm.extend(&[6, 7][..]);
+        dbm.extend((8..10).map(|x| x as u8));
+        assert_eq!(dbm, [1, 2, 3, 4, 5, 6, 7, 8, 9][..]);
+    }
+
+    /// If the iterator panics, the DivBufMut should still include whatever
+    /// bytes were added before the panic.
+    #[test]
+    pub fn extend_panic() {
+        let dbs = DivBufShared::with_capacity(4);
+        let mut dbm = dbs.try_mut().unwrap();
+        dbm.extend(&[1, 2]);
+        let iter = (3..10u8).inspect(|&x| {
+            if x == 6 {
+                panic!("Iterator exploded");
+            }
+        });
+        let r = panic::catch_unwind(AssertUnwindSafe(|| dbm.extend(iter)));
+        assert!(r.is_err());
+        assert_eq!(dbm, [1, 2, 3, 4, 5][..]);
+        assert_eq!(dbs.len(), 5);
+        assert!(dbs.capacity() >= 5);
+        // The DivBufMut should still be terminal
+        dbm.extend(&[6]);
+        assert_eq!(dbm, [1, 2, 3, 4, 5, 6][..]);
+    }
+
+    /// Like extend_panic, but for a DivBufMut that doesn't start at 0
     #[test]
-    pub fn drop_last() {
-        let dbs0 = DivBufShared::from(vec![1, 2, 3]);
-        let _dbm0 = dbs0.try_mut().unwrap();
-        drop(dbs0);
+    pub fn try_extend_panic() {
+        let dbs = DivBufShared::from(vec![1, 2]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let mut dbm1 = dbm0.split_off(1);
+        let iter = (3..10u8).inspect(|&x| {
+            if x == 5 {
+                panic!("Iterator exploded");
+            }
+        });
+        let r = panic::catch_unwind(AssertUnwindSafe(|| dbm1.try_extend(iter)));
+        assert!(r.is_err());
+        assert_eq!(dbm1, [2, 3, 4][..]);
+        assert_eq!(dbs.len(), 4);
+        assert!(dbm0.try_extend(&[0]).is_err());
+        dbm1.try_extend(&[5]).unwrap();
+        assert_eq!(dbm1, [2, 3, 4, 5][..]);
     }
 
     #[test]
-    pub fn eq() {
-        let dbs0 = DivBufShared::from(vec![1, 2, 3]);
-        let dbs1 = DivBufShared::from(vec![1, 2, 3]);
-        let dbs2 = DivBufShared::from(vec![1, 2]);
-        let dbm0 = dbs0.try_mut().unwrap();
-        let dbm1 = dbs1.try_mut().unwrap();
-        let dbm2 = dbs2.try_mut().unwrap();
-        assert_eq!(dbm0, dbm1);
-        assert_ne!(dbm0, dbm2);
+    pub fn fill_from_iter_short() {
+        let dbs = DivBufShared::from(vec![0; 6]);
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            let mut dbm1 = dbm0.split_off(2);
+            let _dbm2 = dbm1.split_off(2);
+            assert_eq!(dbm1.fill_from_iter(vec![7]), 1);
+            assert_eq!(dbm1.len(), 2);
+        }
+        assert_eq!(dbs.try_const().unwrap(), [0, 0, 7, 0, 0, 0][..]);
     }
 
     #[test]
-    pub fn extend() {
-        let dbs = DivBufShared::from(vec![1, 2, 3]);
+    pub fn fill_from_iter_exact() {
+        let dbs = DivBufShared::from(vec![0; 6]);
         {
-            let mut dbm = dbs.try_mut().unwrap();
-            dbm.extend([4, 5, 6].iter());
+            let mut dbm0 = dbs.try_mut().unwrap();
+            let mut dbm1 = dbm0.split_off(2);
+            let _dbm2 = dbm1.split_off(2);
+            assert_eq!(dbm1.fill_from_iter(vec![7, 8]), 2);
         }
-        // verify that dbs.inner.vec was extended
-        let db = dbs.try_const().unwrap();
-        let slice: &[u8] = &db;
-        assert_eq!(slice, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(dbs.try_const().unwrap(), [0, 0, 7, 8, 0, 0][..]);
     }
 
     #[test]
-    #[should_panic(expected = "extend into the middle of a buffer")]
-    pub fn extend_from_the_middle() {
-        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
-        let mut dbm = dbs.try_mut().unwrap();
-        let mut dbm_begin = dbm.split_to(3);
-        dbm_begin.extend([7, 8, 9].iter());
+    pub fn fill_from_iter_long() {
+        let dbs = DivBufShared::from(vec![0; 6]);
+        let mut iter = 1..;
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            let mut dbm1 = dbm0.split_off(2);
+            let _dbm2 = dbm1.split_off(2);
+            assert_eq!(dbm1.fill_from_iter(&mut iter), 2);
+        }
+        assert_eq!(dbs.try_const().unwrap(), [0, 0, 1, 2, 0, 0][..]);
+        // No bytes should be consumed beyond the end of the window
+        assert_eq!(iter.next(), Some(3));
     }
 
     #[test]
@@ -885,6 +3066,23 @@ mod divbuf_mut {
         assert_eq!(simple_hash(&dbm0), expected);
     }
 
+    #[test]
+    pub fn is_all() {
+        let dbs = DivBufShared::from(vec![0; 21]);
+        let mut dbm = dbs.try_mut().unwrap();
+        assert!(dbm.is_all(0));
+        assert!(!dbm.is_all(0xff));
+        for x in dbm.iter_mut() {
+            *x = 0xff;
+        }
+        assert!(dbm.is_all(0xff));
+        dbm[3] = 0;
+        assert!(!dbm.is_all(0xff));
+        dbm[3] = 0xff;
+        dbm[20] = 0;
+        assert!(!dbm.is_all(0xff));
+    }
+
     #[test]
     pub fn is_empty() {
         let dbs0 = DivBufShared::with_capacity(64);
@@ -895,6 +3093,41 @@ mod divbuf_mut {
         assert!(!dbm0.is_empty());
     }
 
+    #[test]
+    pub fn iter_mut() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            let _dbm1 = dbm0.split_off(4);
+            for x in dbm0.iter_mut() {
+                *x += 10;
+            }
+        }
+        assert_eq!(dbs.try_const().unwrap(), [11, 12, 13, 14, 5, 6][..]);
+    }
+
+    #[test]
+    pub fn map_bytes() {
+        let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5]);
+        {
+            let mut dbm = dbs.try_mut().unwrap();
+            let mut right = dbm.split_off(2);
+            let _ = right.split_off(2);
+            right.map_bytes(|x| x ^ 0x55);
+            assert_eq!(right, [0x57, 0x56][..]);
+        }
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db, [0, 1, 0x57, 0x56, 4, 5][..]);
+    }
+
+    #[test]
+    pub fn map_bytes_empty() {
+        let dbs = DivBufShared::from(vec![]);
+        let mut dbm = dbs.try_mut().unwrap();
+        dbm.map_bytes(|_| panic!("should not be called"));
+        assert!(dbm.is_empty());
+    }
+
     #[test]
     pub fn ord() {
         let dbs = DivBufShared::from(vec![0, 1, 0, 2]);
@@ -911,6 +3144,78 @@ mod divbuf_mut {
         assert!(dbm0 < dbm1);
     }
 
+    /// read_from overwrites a window in the middle of the buffer, and reads no
+    /// more than fits.
+    #[test]
+    pub fn read_from() {
+        let dbs = DivBufShared::from(vec![0; 8]);
+        {
+            let mut left = dbs.try_mut().unwrap();
+            let mut middle = left.split_off(2);
+            let _right = middle.split_off(4);
+            let mut cursor = Cursor::new(b"abcdefg");
+            assert_eq!(middle.read_from(&mut cursor).unwrap(), 4);
+            assert_eq!(cursor.position(), 4);
+        }
+        assert_eq!(dbs.try_const().unwrap(), b"\0\0abcd\0\0"[..]);
+        assert_eq!(dbs.len(), 8);
+    }
+
+    #[test]
+    pub fn read_from_eof() {
+        let dbs = DivBufShared::from(vec![0; 6]);
+        let mut dbm = dbs.try_mut().unwrap();
+        let mut cursor = Cursor::new(b"ab");
+        assert_eq!(dbm.read_from(&mut cursor).unwrap(), 2);
+        assert_eq!(dbm, b"ab\0\0\0\0"[..]);
+    }
+
+    /// Short and interrupted reads are retried until the window is full
+    #[test]
+    pub fn read_from_short_reads() {
+        struct Dribble(Cursor<&'static [u8]>, bool);
+        impl Read for Dribble {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                self.1 = !self.1;
+                if self.1 {
+                    Err(io::Error::from(io::ErrorKind::Interrupted))
+                } else {
+                    let l = buf.len().min(1);
+                    self.0.read(&mut buf[..l])
+                }
+            }
+        }
+
+        let dbs = DivBufShared::from(vec![0; 3]);
+        let mut dbm = dbs.try_mut().unwrap();
+        let mut r = Dribble(Cursor::new(&b"xyz"[..]), false);
+        assert_eq!(dbm.read_from(&mut r).unwrap(), 3);
+        assert_eq!(dbm, b"xyz"[..]);
+    }
+
+    #[test]
+    pub fn remaining_capacity() {
+        let dbs = DivBufShared::with_capacity(64);
+        let mut dbm = dbs.try_mut().unwrap();
+        let cap = dbs.capacity();
+        assert_eq!(dbm.remaining_capacity(), cap);
+        dbm.extend(&[1, 2, 3, 4]);
+        assert_eq!(dbm.remaining_capacity(), cap - 4);
+        dbm.reserve(cap);
+        assert_eq!(dbm.remaining_capacity(), dbs.capacity() - 4);
+        assert!(dbm.remaining_capacity() >= cap);
+    }
+
+    #[test]
+    pub fn remaining_capacity_nonterminal() {
+        let dbs = DivBufShared::with_capacity(64);
+        let mut dbm = dbs.try_mut().unwrap();
+        dbm.extend(&[1, 2, 3, 4]);
+        let dbm1 = dbm.split_off(2);
+        assert_eq!(dbm.remaining_capacity(), 0);
+        assert_eq!(dbm1.remaining_capacity(), dbs.capacity() - 4);
+    }
+
     #[test]
     pub fn reserve() {
         let v = Vec::<u8>::with_capacity(64);
@@ -930,6 +3235,31 @@ mod divbuf_mut {
         left_half.reserve(128);
     }
 
+    #[test]
+    pub fn resize() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let _ = dbm0.split_to(1);
+        dbm0.resize(5, 9);
+        assert_eq!(dbm0, [2, 3, 4, 9, 9][..]);
+        assert_eq!(dbs.len(), 6);
+        dbm0.resize(1, 9);
+        assert_eq!(dbm0, [2][..]);
+        assert_eq!(dbs.len(), 2);
+        dbm0.resize(0, 9);
+        assert!(dbm0.is_empty());
+        assert_eq!(dbs.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't resize from a non-terminal buffer")]
+    pub fn resize_nonterminal() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let _dbm1 = dbm0.split_off(2);
+        dbm0.resize(3, 0);
+    }
+
     #[test]
     pub fn send() {
         let dbs = DivBufShared::with_capacity(4096);
@@ -941,6 +3271,27 @@ mod divbuf_mut {
         .unwrap();
     }
 
+    #[test]
+    pub fn swap() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm = dbs.try_mut().unwrap();
+        let mut window = dbm.split_off(1);
+        let _tail = window.split_off(4);
+        window.swap(0, 3);
+        assert_eq!(window, [5, 3, 4, 2][..]);
+        window.swap(1, 1);
+        assert_eq!(window, [5, 3, 4, 2][..]);
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn swap_out_of_bounds() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm = dbs.try_mut().unwrap();
+        let _tail = dbm.split_off(3);
+        dbm.swap(0, 3);
+    }
+
     #[test]
     pub fn sync() {
         lazy_static! {
@@ -957,77 +3308,337 @@ mod divbuf_mut {
     }
 
     #[test]
-    pub fn split_off() {
-        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    pub fn set_len() {
+        let dbs = DivBufShared::with_capacity(64);
+        let mut dbm = dbs.try_mut().unwrap();
+        dbm.extend(&[1, 2]);
+        let mut dbm1 = dbm.split_off(1);
+        let spare = dbm1.spare_capacity_mut();
+        assert_eq!(spare.len(), dbs.capacity() - 2);
+        for (i, b) in spare[..3].iter_mut().enumerate() {
+            *b = MaybeUninit::new(10 + i as u8);
+        }
+        unsafe { dbm1.set_len(4) };
+        assert_eq!(dbm1, [2, 10, 11, 12][..]);
+        assert_eq!(dbs.len(), 5);
+        // Shrinking a terminal DivBufMut shrinks the buffer, too
+        unsafe { dbm1.set_len(2) };
+        assert_eq!(dbm1, [2, 10][..]);
+        assert_eq!(dbs.len(), 3);
+    }
+
+    #[test]
+    pub fn set_len_nonterminal() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let mut dbm = dbs.try_mut().unwrap();
+        let dbm1 = dbm.split_off(2);
+        assert!(dbm.spare_capacity_mut().is_empty());
+        unsafe { dbm.set_len(1) };
+        assert_eq!(dbm, [1][..]);
+        assert_eq!(dbm1, [3, 4][..]);
+        assert_eq!(dbs.len(), 4);
+    }
+
+    #[test]
+    pub fn split_off() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        // split in the middle
+        let dbm_mid = dbm0.split_off(4);
+        assert_eq!(dbm0, [1, 2, 3, 4][..]);
+        assert_eq!(dbm0.len(), 4);
+        assert_eq!(dbm_mid, [5, 6][..]);
+        assert_eq!(dbm_mid.len(), 2);
+        // split at the beginning
+        let mut dbm_begin = dbm0.split_off(0);
+        assert_eq!(dbm0, [][..]);
+        assert_eq!(dbm_begin, [1, 2, 3, 4][..]);
+        // split at the end
+        let dbm_end = dbm_begin.split_off(4);
+        assert_eq!(dbm_begin, [1, 2, 3, 4][..]);
+        assert_eq!(dbm_end, [][..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't split past the end")]
+    pub fn split_off_past_the_end() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        dbm0.split_off(7);
+    }
+
+    #[test]
+    pub fn split_to() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        // split in the middle
+        let mut dbm_left = dbm0.split_to(4);
+        assert_eq!(dbm_left, [1, 2, 3, 4][..]);
+        assert_eq!(dbm_left.len(), 4);
+        assert_eq!(dbm0, [5, 6][..]);
+        assert_eq!(dbm0.len(), 2);
+        // split at the beginning
+        let dbm_begin = dbm_left.split_to(0);
+        assert_eq!(dbm_begin, [][..]);
+        assert_eq!(dbm_left, [1, 2, 3, 4][..]);
+        // split at the end
+        let dbm_mid = dbm_left.split_to(4);
+        assert_eq!(dbm_mid, [1, 2, 3, 4][..]);
+        assert_eq!(dbm_left, [][..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't split past the end")]
+    pub fn split_to_past_the_end() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        dbm0.split_to(7);
+    }
+
+    #[test]
+    pub fn take() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let dbs_ref = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm_ref = dbs_ref.try_mut().unwrap();
+        for &n in &[2, 0, 4] {
+            assert_eq!(dbm0.take(n), dbm_ref.split_to(n));
+            assert_eq!(dbm0, dbm_ref);
+        }
+        assert!(dbm0.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't split past the end")]
+    pub fn take_past_the_end() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        dbm0.take(7);
+    }
+
+    #[test]
+    pub fn to_shared() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3, 4]);
+        let mut dbm = dbs0.try_mut().unwrap();
+        dbm[0] = 5;
+        let dbs1 = dbm.to_shared();
+        // Both owners see the same storage, and the writer is still active
+        assert!(dbs1.try_const().is_err());
+        drop(dbm);
+        drop(dbs0);
+        assert_eq!(dbs1.try_const().unwrap(), [5, 2, 3, 4][..]);
+        let v: Vec<u8> = dbs1.try_into().unwrap();
+        assert_eq!(v, vec![5, 2, 3, 4]);
+    }
+
+    #[test]
+    pub fn truncate() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let _ = dbm0.split_to(1);
+        // Truncating past the end has no effect
+        dbm0.truncate(7);
+        assert_eq!(dbm0, [2, 3, 4, 5, 6][..]);
+        dbm0.truncate(2);
+        assert_eq!(dbm0, [2, 3][..]);
+        assert_eq!(dbs.len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't truncate a non-terminal DivBufMut")]
+    pub fn truncate_nonterminal() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let _dbm1 = dbm0.split_off(4);
+        dbm0.truncate(2);
+    }
+
+    #[test]
+    pub fn try_append() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3]);
+        let dbs1 = DivBufShared::from(vec![4, 5, 6, 7]);
+        let src = dbs1.try_const().unwrap().slice(1, 3);
+        {
+            let mut dbm = dbs0.try_mut().unwrap();
+            let mut dbm = dbm.split_off(2);
+            dbm.try_append(&src).unwrap();
+            assert_eq!(dbm, [3, 5, 6][..]);
+        }
+        assert_eq!(dbs0.try_const().unwrap(), [1, 2, 3, 5, 6][..]);
+        // The source is unaffected
+        assert_eq!(src, [5, 6][..]);
+    }
+
+    #[test]
+    pub fn try_append_nonterminal() {
+        let dbs0 = DivBufShared::from(vec![1, 2, 3]);
+        let dbs1 = DivBufShared::from(vec![4, 5]);
+        let src = dbs1.try_const().unwrap();
+        let mut dbm = dbs0.try_mut().unwrap();
+        let _tail = dbm.split_off(2);
+        assert!(dbm.try_append(&src).is_err());
+        assert_eq!(dbm, [1, 2][..]);
+        assert_eq!(dbs0.len(), 3);
+    }
+
+    #[test]
+    pub fn try_extend() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            assert!(dbm0.try_extend([4, 5, 6].iter()).is_ok());
+
+            // Extending from the middle of the vec should fail
+            let mut dbm1 = dbm0.split_to(2);
+            assert!(dbm1.try_extend([7, 8, 9].iter()).is_err());
+        }
+
+        // verify that dbs.inner.vec was extended the first time, but not the
+        // second.
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db, [1, 2, 3, 4, 5, 6][..]);
+    }
+
+    #[test]
+    pub fn try_extend_from_slice() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let _ = dbm0.split_to(1);
+        dbm0.try_extend_from_slice(&[4, 5, 6]).unwrap();
+        assert_eq!(dbm0, [2, 3, 4, 5, 6][..]);
+        assert_eq!(dbs.len(), 6);
+    }
+
+    #[test]
+    pub fn try_extend_from_slice_nonterminal() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let _dbm1 = dbm0.split_off(1);
+        assert!(dbm0.try_extend_from_slice(&[4, 5, 6]).is_err());
+        assert_eq!(dbm0, [1][..]);
+        assert_eq!(dbs.len(), 3);
+    }
+
+    #[test]
+    pub fn try_extend_owned() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            assert!(dbm0.try_extend(vec![4, 5, 6]).is_ok());
+            assert!(dbm0.try_extend(&[7u8][..]).is_ok());
+
+            let mut dbm1 = dbm0.split_to(2);
+            assert!(dbm1.try_extend(vec![8, 9]).is_err());
+        }
+        let db = dbs.try_const().unwrap();
+        assert_eq!(db, [1, 2, 3, 4, 5, 6, 7][..]);
+    }
+
+    #[test]
+    pub fn try_extend_repeat() {
+        let dbs = DivBufShared::with_capacity(64);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        dbm0.try_extend(b"abc").unwrap();
+        let _ = dbm0.split_to(1);
+        dbm0.try_extend_repeat(b'-', 5).unwrap();
+        assert_eq!(dbm0, b"bc-----"[..]);
+        dbm0.try_extend_repeat(b'x', 0).unwrap();
+        assert_eq!(dbm0, b"bc-----"[..]);
+        assert_eq!(dbs.len(), 8);
+    }
+
+    #[test]
+    pub fn try_extend_repeat_nonterminal() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let _dbm1 = dbm0.split_off(1);
+        assert!(dbm0.try_extend_repeat(0, 4).is_err());
+        assert_eq!(dbm0, [1][..]);
+        assert_eq!(dbs.len(), 3);
+    }
+
+    #[test]
+    pub fn try_freeze_nonempty() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
         let mut dbm0 = dbs.try_mut().unwrap();
-        // split in the middle
-        let dbm_mid = dbm0.split_off(4);
-        assert_eq!(dbm0, [1, 2, 3, 4][..]);
-        assert_eq!(dbm0.len(), 4);
-        assert_eq!(dbm_mid, [5, 6][..]);
-        assert_eq!(dbm_mid.len(), 2);
-        // split at the beginning
-        let mut dbm_begin = dbm0.split_off(0);
-        assert_eq!(dbm0, [][..]);
-        assert_eq!(dbm_begin, [1, 2, 3, 4][..]);
-        // split at the end
-        let dbm_end = dbm_begin.split_off(4);
-        assert_eq!(dbm_begin, [1, 2, 3, 4][..]);
-        assert_eq!(dbm_end, [][..]);
+        let _dbm1 = dbm0.split_off(2);
+        let db = dbm0.try_freeze_nonempty().unwrap();
+        assert_eq!(db, [1, 2][..]);
     }
 
     #[test]
-    #[should_panic(expected = "Can't split past the end")]
-    pub fn split_off_past_the_end() {
-        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+    pub fn try_freeze_nonempty_empty() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
         let mut dbm0 = dbs.try_mut().unwrap();
-        dbm0.split_off(7);
+        let dbm1 = dbm0.split_off(4);
+        let mut dbm1 = dbm1.try_freeze_nonempty().unwrap_err();
+        // The returned DivBufMut is still usable
+        dbm1.extend(&[5]);
+        assert_eq!(dbm1, [5][..]);
     }
 
     #[test]
-    pub fn split_to() {
+    pub fn try_into_chunks() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let dbm = dbs.try_mut().unwrap();
+        let mut chunks = dbm.try_into_chunks(3).unwrap();
+        assert_eq!(chunks.next().unwrap(), [1, 2, 3][..]);
+        assert_eq!(chunks.next().unwrap(), [4, 5][..]);
+        assert!(chunks.next().is_none());
+    }
+
+    #[test]
+    pub fn try_into_chunks_zero() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let dbm = dbs.try_mut().unwrap();
+        let (mut dbm, _) = dbm.try_into_chunks(0).unwrap_err();
+        // The returned DivBufMut is still usable
+        dbm[0] = 9;
+        assert_eq!(dbm, [9, 2, 3, 4, 5][..]);
+    }
+
+    #[test]
+    pub fn try_remove_range() {
         let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
         let mut dbm0 = dbs.try_mut().unwrap();
-        // split in the middle
-        let mut dbm_left = dbm0.split_to(4);
-        assert_eq!(dbm_left, [1, 2, 3, 4][..]);
-        assert_eq!(dbm_left.len(), 4);
-        assert_eq!(dbm0, [5, 6][..]);
-        assert_eq!(dbm0.len(), 2);
-        // split at the beginning
-        let dbm_begin = dbm_left.split_to(0);
-        assert_eq!(dbm_begin, [][..]);
-        assert_eq!(dbm_left, [1, 2, 3, 4][..]);
-        // split at the end
-        let dbm_mid = dbm_left.split_to(4);
-        assert_eq!(dbm_mid, [1, 2, 3, 4][..]);
-        assert_eq!(dbm_left, [][..]);
+        // Remove from the start
+        assert!(dbm0.try_remove_range(..2).is_ok());
+        assert_eq!(dbm0, [3, 4, 5, 6][..]);
+        assert_eq!(dbs.len(), 4);
+        // Remove from the middle, shifting the tail left
+        assert!(dbm0.try_remove_range(1..=2).is_ok());
+        assert_eq!(dbm0, [3, 6][..]);
+        assert_eq!(dbs.len(), 2);
+        // Remove the whole buffer
+        assert!(dbm0.try_remove_range(..).is_ok());
+        assert!(dbm0.is_empty());
+        assert!(dbs.is_empty());
     }
 
     #[test]
-    #[should_panic(expected = "Can't split past the end")]
-    pub fn split_to_past_the_end() {
+    pub fn try_remove_range_nonterminal() {
         let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
         let mut dbm0 = dbs.try_mut().unwrap();
-        dbm0.split_to(7);
+        let mut dbm1 = dbm0.split_to(3);
+        assert!(dbm1.try_remove_range(0..1).is_err());
+        assert_eq!(dbm1, [1, 2, 3][..]);
+        // Removing from a terminal buffer that doesn't start at the beginning
+        // should work
+        assert!(dbm0.try_remove_range(0..1).is_ok());
+        assert_eq!(dbm0, [5, 6][..]);
+        assert_eq!(dbs.len(), 5);
     }
 
     #[test]
-    pub fn try_extend() {
-        let dbs = DivBufShared::from(vec![1, 2, 3]);
-        {
-            let mut dbm0 = dbs.try_mut().unwrap();
-            assert!(dbm0.try_extend([4, 5, 6].iter()).is_ok());
-
-            // Extending from the middle of the vec should fail
-            let mut dbm1 = dbm0.split_to(2);
-            assert!(dbm1.try_extend([7, 8, 9].iter()).is_err());
-        }
-
-        // verify that dbs.inner.vec was extended the first time, but not the
-        // second.
-        let db = dbs.try_const().unwrap();
-        assert_eq!(db, [1, 2, 3, 4, 5, 6][..]);
+    pub fn try_remove_range_out_of_bounds() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let mut dbm1 = dbm0.split_off(2);
+        assert!(dbm1.try_remove_range(2..5).is_err());
+        #[allow(clippy::reversed_empty_ranges)]
+        let backwards = 3..1;
+        assert!(dbm1.try_remove_range(backwards).is_err());
+        assert_eq!(dbm1, [3, 4, 5, 6][..]);
     }
 
     #[test]
@@ -1056,6 +3667,45 @@ mod divbuf_mut {
         }
     }
 
+    #[test]
+    pub fn try_retain() {
+        let dbs = DivBufShared::from(vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            let mut dbm1 = dbm0.split_off(2);
+            let mut odd = false;
+            dbm1.try_retain(|_| {
+                odd = !odd;
+                odd
+            })
+            .unwrap();
+            assert_eq!(dbm1, [2, 4, 6][..]);
+            assert_eq!(dbm0, [0, 1][..]);
+        }
+        assert_eq!(dbs.len(), 5);
+        assert_eq!(dbs.try_const().unwrap(), [0, 1, 2, 4, 6][..]);
+    }
+
+    #[test]
+    pub fn try_retain_all() {
+        let dbs = DivBufShared::from(vec![0, 1, 2, 3]);
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            dbm0.try_retain(|_| true).unwrap();
+            assert_eq!(dbm0, [0, 1, 2, 3][..]);
+        }
+        assert_eq!(dbs.len(), 4);
+    }
+
+    #[test]
+    pub fn try_retain_nonterminal() {
+        let dbs = DivBufShared::from(vec![0, 1, 2, 3]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let _dbm1 = dbm0.split_off(2);
+        assert!(dbm0.try_retain(|b| b == 0).is_err());
+        assert_eq!(dbm0, [0, 1][..]);
+    }
+
     #[test]
     pub fn try_truncate() {
         let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
@@ -1127,6 +3777,95 @@ mod divbuf_mut {
         assert!(dbm0.write("ABCD".as_bytes()).is_err());
     }
 
+    #[test]
+    pub fn write_array() {
+        let dbs = DivBufShared::from(vec![0; 8]);
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            let mut dbm1 = dbm0.split_off(2);
+            let _dbm2 = dbm1.split_off(4);
+            dbm1.write_array(0, [1]).unwrap();
+            dbm1.write_array(2, [2, 3]).unwrap();
+            dbm1.write_array(4, []).unwrap();
+            assert_eq!(dbm1.len(), 4);
+        }
+        assert_eq!(dbs.try_const().unwrap(), [0, 0, 1, 0, 2, 3, 0, 0][..]);
+    }
+
+    #[test]
+    pub fn write_array_out_of_range() {
+        let dbs = DivBufShared::from(vec![0; 8]);
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            let mut dbm1 = dbm0.split_off(2);
+            let _dbm2 = dbm1.split_off(4);
+            assert!(dbm1.write_array(3, [1, 2]).is_err());
+            assert!(dbm1.write_array(5, []).is_err());
+            assert!(dbm1.write_array(usize::MAX, [1]).is_err());
+        }
+        assert_eq!(dbs.try_const().unwrap(), [0; 8][..]);
+    }
+
+    #[test]
+    pub fn write_at_start() {
+        let dbs = DivBufShared::from(vec![0; 6]);
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            let mut dbm1 = dbm0.split_off(2);
+            let _dbm2 = dbm1.split_off(2);
+            assert_eq!(dbm1.write_at(0, &[1]).unwrap(), 1);
+            assert_eq!(dbm1.len(), 2);
+        }
+        assert_eq!(dbs.try_const().unwrap(), [0, 0, 1, 0, 0, 0][..]);
+    }
+
+    #[test]
+    pub fn write_at_clamped() {
+        let dbs = DivBufShared::from(vec![0; 6]);
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            let mut dbm1 = dbm0.split_off(2);
+            let _dbm2 = dbm1.split_off(2);
+            assert_eq!(dbm1.write_at(1, &[1, 2, 3]).unwrap(), 1);
+            assert_eq!(dbm1.write_at(2, &[4]).unwrap(), 0);
+        }
+        assert_eq!(dbs.try_const().unwrap(), [0, 0, 0, 1, 0, 0][..]);
+    }
+
+    #[test]
+    pub fn write_at_out_of_range() {
+        let dbs = DivBufShared::from(vec![0; 6]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let _dbm1 = dbm0.split_off(2);
+        assert!(dbm0.write_at(3, &[1]).is_err());
+        assert_eq!(dbm0, [0, 0][..]);
+    }
+
+    #[test]
+    pub fn write_frame_u32_be() {
+        let dbs = DivBufShared::with_capacity(64);
+        {
+            let mut dbm = dbs.try_mut().unwrap();
+            dbm.write_frame_u32_be(b"hello").unwrap();
+            dbm.write_frame_u32_be(b"").unwrap();
+            assert_eq!(dbm, b"\0\0\0\x05hello\0\0\0\0"[..]);
+        }
+        let mut db = dbs.try_const().unwrap();
+        assert_eq!(db.read_frame_u32_be().unwrap(), b"hello"[..]);
+        assert_eq!(db.read_frame_u32_be().unwrap(), b""[..]);
+        assert!(db.is_empty());
+    }
+
+    #[test]
+    pub fn write_frame_u32_be_nonterminal() {
+        let dbs = DivBufShared::from(vec![0, 1, 2, 3]);
+        let mut dbm0 = dbs.try_mut().unwrap();
+        let _dbm1 = dbm0.split_off(2);
+        assert!(dbm0.write_frame_u32_be(b"hello").is_err());
+        assert_eq!(dbm0, [0, 1][..]);
+        assert_eq!(dbs.len(), 4);
+    }
+
     #[test]
     pub fn write_all() {
         let dbs0 = DivBufShared::with_capacity(0);
@@ -1143,6 +3882,46 @@ mod divbuf_mut {
         assert!(dbm0.write_all("ABCD".as_bytes()).is_err());
     }
 
+    /// write_all must produce the same result as try_extend, in the middle of
+    /// a buffer's spare capacity as well as when it reallocates.
+    #[test]
+    pub fn write_all_matches_try_extend() {
+        let src = (0..=255).cycle().take(5000).collect::<Vec<u8>>();
+        let dbs0 = DivBufShared::from(vec![1, 2, 3]);
+        let dbs1 = DivBufShared::from(vec![1, 2, 3]);
+        let mut dbm0 = dbs0.try_mut().unwrap();
+        let mut dbm1 = dbs1.try_mut().unwrap();
+        for chunk in src.chunks(1000) {
+            dbm0.write_all(chunk).unwrap();
+            dbm1.try_extend(chunk).unwrap();
+        }
+        assert_eq!(dbm0, dbm1);
+        assert_eq!(dbm0.len(), 5003);
+        drop(dbm0);
+        drop(dbm1);
+        assert_eq!(dbs0.len(), dbs1.len());
+    }
+
+    #[test]
+    pub fn write_vectored() {
+        let dbs0 = DivBufShared::from(vec![0]);
+        let mut dbm0 = dbs0.try_mut().unwrap();
+        let bufs = [IoSlice::new(b"AB"), IoSlice::new(b"CDE")];
+        assert_eq!(dbm0.write_vectored(&bufs).unwrap(), 5);
+        assert_eq!(&dbm0[..], &b"\0ABCDE"[..]);
+        assert_eq!(dbs0.len(), 6);
+    }
+
+    #[test]
+    pub fn write_vectored_nonterminal() {
+        let dbs0 = DivBufShared::from(vec![0, 1, 2, 3]);
+        let mut dbm0 = dbs0.try_mut().unwrap();
+        let _ = dbm0.split_off(2);
+        let bufs = [IoSlice::new(b"AB"), IoSlice::new(b"CD")];
+        assert!(dbm0.write_vectored(&bufs).is_err());
+        assert_eq!(&dbm0[..], &[0, 1][..]);
+    }
+
     #[test]
     pub fn flush() {
         let dbs0 = DivBufShared::with_capacity(0);
@@ -1151,4 +3930,319 @@ mod divbuf_mut {
         dbm0.flush().unwrap();
         assert_eq!(&dbm0[..], &[65u8, 66u8, 67u8, 68u8][..])
     }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    pub fn zeroize() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        {
+            let mut dbm0 = dbs.try_mut().unwrap();
+            let mut dbm1 = dbm0.split_off(2);
+            let _dbm2 = dbm1.split_off(2);
+            dbm1.zeroize();
+            assert_eq!(dbm1, [0, 0][..]);
+            assert_eq!(dbm1.len(), 2);
+        }
+        assert_eq!(dbs.try_const().unwrap(), [1, 2, 0, 0, 5, 6][..]);
+    }
+}
+
+//
+// DivBufPool methods
+//
+mod divbuf_pool {
+    use super::*;
+
+    fn storage(dbs: &DivBufShared) -> *const u8 {
+        dbs.try_const().unwrap().as_ptr()
+    }
+
+    #[test]
+    pub fn get() {
+        let pool = DivBufPool::new(64);
+        let dbs = pool.get();
+        assert!(dbs.is_empty());
+        assert!(dbs.capacity() >= 64);
+        assert_eq!(pool.capacity(), 64);
+        assert_eq!(pool.idle(), 0);
+    }
+
+    /// Storage that was taken out of the buffer can't be recycled
+    #[test]
+    pub fn into_vec() {
+        let pool = DivBufPool::new(64);
+        let dbs = pool.get();
+        let db = dbs.try_const().unwrap();
+        drop(dbs);
+        let v = db.into_vec().unwrap();
+        assert!(v.capacity() >= 64);
+        assert_eq!(pool.idle(), 0);
+    }
+
+    /// Storage outlives the pool, and gets freed normally
+    #[test]
+    pub fn pool_dropped_first() {
+        let pool = DivBufPool::new(64);
+        let dbs = pool.get();
+        drop(pool);
+        dbs.try_mut().unwrap().extend(&[1, 2, 3]);
+        assert_eq!(dbs.try_const().unwrap(), [1, 2, 3][..]);
+    }
+
+    /// Storage gets recycled only once the last child is dropped
+    #[test]
+    pub fn recycle_after_children() {
+        let pool = DivBufPool::new(64);
+        let dbs = pool.get();
+        let ptr = storage(&dbs);
+        let db = dbs.try_const().unwrap();
+        drop(dbs);
+        assert_eq!(pool.idle(), 0);
+        drop(db);
+        assert_eq!(pool.idle(), 1);
+        assert_eq!(storage(&pool.get()), ptr);
+    }
+
+    /// Recycled storage should be emptied before reuse
+    #[test]
+    pub fn recycle_nonempty() {
+        let pool = DivBufPool::new(64);
+        let dbs = pool.get();
+        let ptr = storage(&dbs);
+        dbs.try_mut().unwrap().extend(&[1, 2, 3]);
+        drop(dbs);
+        let dbs = pool.get();
+        assert!(dbs.is_empty());
+        assert_eq!(storage(&dbs), ptr);
+    }
+
+    #[test]
+    pub fn reuse() {
+        let pool = DivBufPool::new(64);
+        let dbs0 = pool.get();
+        let dbs1 = pool.get();
+        let ptr0 = storage(&dbs0);
+        let ptr1 = storage(&dbs1);
+        assert_ne!(ptr0, ptr1);
+        drop(dbs0);
+        drop(dbs1);
+        assert_eq!(pool.idle(), 2);
+        for _ in 0..4 {
+            let dbs0 = pool.get();
+            let dbs1 = pool.get();
+            assert_eq!(pool.idle(), 0);
+            let mut ptrs = vec![storage(&dbs0), storage(&dbs1)];
+            ptrs.sort();
+            let mut expected = vec![ptr0, ptr1];
+            expected.sort();
+            assert_eq!(ptrs, expected);
+        }
+        assert_eq!(pool.idle(), 2);
+    }
+
+    /// Releasing a buffer with try_into_idle returns its storage to the pool
+    #[test]
+    pub fn try_into_idle() {
+        let pool = DivBufPool::new(64);
+        let dbs = pool.get();
+        let ptr = storage(&dbs);
+        let db = dbs.try_const().unwrap();
+        let dbs = dbs.try_into_idle().unwrap_err();
+        drop(db);
+        assert_eq!(pool.idle(), 0);
+        dbs.try_into_idle().unwrap();
+        assert_eq!(pool.idle(), 1);
+        assert_eq!(storage(&pool.get()), ptr);
+    }
+
+    /// Storage that was taken out of the buffer can't be recycled
+    #[test]
+    pub fn try_into_vec() {
+        let pool = DivBufPool::new(64);
+        let v = Vec::try_from(pool.get()).unwrap();
+        assert!(v.capacity() >= 64);
+        assert_eq!(pool.idle(), 0);
+    }
+}
+
+//
+// DivBufReader methods
+//
+mod divbuf_reader {
+    use super::*;
+
+    #[test]
+    pub fn read() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let mut reader = dbs.try_const().unwrap().reader();
+        let mut buf = [0; 3];
+        assert_eq!(reader.read(&mut buf[..]).unwrap(), 3);
+        assert_eq!(buf, [1, 2, 3]);
+        assert_eq!(reader.read(&mut buf[..]).unwrap(), 2);
+        assert_eq!(buf[..2], [4, 5]);
+        assert_eq!(reader.read(&mut buf[..]).unwrap(), 0);
+        assert_eq!(reader.position(), 5);
+    }
+
+    #[test]
+    pub fn read_split() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let mut db = dbs.try_const().unwrap();
+        let _ = db.split_to(1);
+        let _ = db.split_off(3);
+        let mut reader = db.reader();
+        let mut v = Vec::new();
+        reader.read_to_end(&mut v).unwrap();
+        assert_eq!(v, [2, 3, 4]);
+        assert_eq!(reader.into_inner(), [2, 3, 4][..]);
+    }
+
+    #[test]
+    pub fn seek_current() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let mut reader = dbs.try_const().unwrap().reader();
+        let mut buf = [0; 1];
+        assert_eq!(reader.seek(SeekFrom::Current(3)).unwrap(), 3);
+        reader.read_exact(&mut buf[..]).unwrap();
+        assert_eq!(buf, [4]);
+        assert_eq!(reader.seek(SeekFrom::Current(-2)).unwrap(), 2);
+        reader.read_exact(&mut buf[..]).unwrap();
+        assert_eq!(buf, [3]);
+        // Seeking out of bounds clamps
+        assert_eq!(reader.seek(SeekFrom::Current(-10)).unwrap(), 0);
+        reader.read_exact(&mut buf[..]).unwrap();
+        assert_eq!(buf, [1]);
+        assert_eq!(reader.seek(SeekFrom::Current(10)).unwrap(), 5);
+        assert_eq!(reader.read(&mut buf[..]).unwrap(), 0);
+    }
+
+    #[test]
+    pub fn seek_end() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let mut reader = dbs.try_const().unwrap().reader();
+        let mut buf = [0; 2];
+        assert_eq!(reader.seek(SeekFrom::End(-2)).unwrap(), 3);
+        reader.read_exact(&mut buf[..]).unwrap();
+        assert_eq!(buf, [4, 5]);
+        assert_eq!(reader.seek(SeekFrom::End(1)).unwrap(), 5);
+        assert_eq!(reader.read(&mut buf[..]).unwrap(), 0);
+        assert_eq!(reader.seek(SeekFrom::End(-6)).unwrap(), 0);
+        reader.read_exact(&mut buf[..]).unwrap();
+        assert_eq!(buf, [1, 2]);
+    }
+
+    #[test]
+    pub fn seek_start() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5]);
+        let mut reader = dbs.try_const().unwrap().reader();
+        let mut buf = [0; 2];
+        reader.read_exact(&mut buf[..]).unwrap();
+        assert_eq!(reader.seek(SeekFrom::Start(0)).unwrap(), 0);
+        reader.read_exact(&mut buf[..]).unwrap();
+        assert_eq!(buf, [1, 2]);
+        assert_eq!(reader.seek(SeekFrom::Start(3)).unwrap(), 3);
+        reader.read_exact(&mut buf[..]).unwrap();
+        assert_eq!(buf, [4, 5]);
+        assert_eq!(reader.seek(SeekFrom::Start(u64::MAX)).unwrap(), 5);
+        assert_eq!(reader.read(&mut buf[..]).unwrap(), 0);
+    }
+}
+
+//
+// HashedDivBuf methods
+//
+mod hashed_divbuf {
+    use super::*;
+
+    #[test]
+    pub fn cached_hash() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 5, 6]);
+        let mut db = dbs.try_const().unwrap();
+        let _ = db.split_to(2);
+        let expected = simple_hash(&db);
+        let hdb = HashedDivBuf::new(db);
+        assert_eq!(hdb.cached_hash(), expected);
+        assert_eq!(hdb.cached_hash(), simple_hash(hdb.get_ref()));
+    }
+
+    #[test]
+    pub fn eq() {
+        let dbs = DivBufShared::from(vec![1, 2, 1, 2, 3, 4]);
+        let db = dbs.try_const().unwrap();
+        let hdb0 = HashedDivBuf::new(db.slice(0, 2));
+        let hdb1 = HashedDivBuf::from(db.slice(2, 4));
+        let hdb2 = HashedDivBuf::new(db.slice(4, 6));
+        assert_eq!(hdb0, hdb1);
+        assert_ne!(hdb0, hdb2);
+    }
+
+    #[test]
+    pub fn hashmap_key() {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4, 1, 2]);
+        let db = dbs.try_const().unwrap();
+        let mut map = HashMap::new();
+        map.insert(HashedDivBuf::new(db.slice(0, 2)), 0);
+        map.insert(HashedDivBuf::new(db.slice(2, 4)), 1);
+        let key = HashedDivBuf::new(db.slice(4, 6));
+        assert_eq!(map.get(&key), Some(&0));
+        // The same key may be probed repeatedly without rehashing its data
+        assert_eq!(simple_hash(&key), simple_hash(&key));
+    }
+
+    #[test]
+    pub fn into_inner() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let hdb = HashedDivBuf::new(dbs.try_const().unwrap());
+        assert_eq!(hdb.as_ref(), &[1, 2, 3][..]);
+        let db = hdb.into_inner();
+        assert_eq!(db, [1, 2, 3][..]);
+    }
+}
+
+//
+// MutGuard methods
+//
+mod mut_guard {
+    use super::*;
+
+    #[test]
+    pub fn deref_mut() {
+        let dbs = DivBufShared::with_capacity(64);
+        {
+            let mut guard = dbs.mut_scope().unwrap();
+            guard.try_extend(&[1, 2, 3]).unwrap();
+            guard[1] = 0;
+            assert_eq!(guard.len(), 3);
+        }
+        assert_eq!(dbs.try_const().unwrap(), [1, 0, 3][..]);
+    }
+
+    #[test]
+    pub fn drop_releases_writer() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let guard = dbs.mut_scope().unwrap();
+        assert!(dbs.try_mut().is_err());
+        assert!(dbs.try_const().is_err());
+        drop(guard);
+        assert!(dbs.try_mut().is_ok());
+        assert!(dbs.mut_scope().is_ok());
+    }
+
+    #[test]
+    pub fn into_inner() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let dbm = dbs.mut_scope().unwrap().into_inner();
+        assert!(dbs.try_mut().is_err());
+        drop(dbm);
+        assert!(dbs.try_mut().is_ok());
+    }
+
+    #[test]
+    pub fn mut_scope_with_reader() {
+        let dbs = DivBufShared::from(vec![1, 2, 3]);
+        let db = dbs.try_const().unwrap();
+        assert!(dbs.mut_scope().is_err());
+        drop(db);
+        assert!(dbs.mut_scope().is_ok());
+    }
 }
@@ -0,0 +1,83 @@
+// vim: tw=80
+//! Model-checked concurrency tests.  Run them like this:
+//! `cargo test --release --features loom --test loom`
+#![cfg(feature = "loom")]
+
+use std::convert::TryInto;
+
+use divbuf::*;
+use loom::thread;
+
+/// Drop the owning DivBufShared and the last DivBuf concurrently.  Exactly one
+/// of them must free the storage.
+#[test]
+fn drop_shared_vs_drop_divbuf() {
+    loom::model(|| {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let db = dbs.try_const().unwrap();
+        let t = thread::spawn(move || drop(db));
+        drop(dbs);
+        t.join().unwrap();
+    });
+}
+
+/// Slice and drop DivBufs in one thread while another tries to upgrade.  Once
+/// the readers are gone, the writer must be able to get in, and the storage
+/// must be reclaimable.
+#[test]
+fn slice_and_drop_vs_try_mut() {
+    loom::model(|| {
+        let dbs = DivBufShared::from(vec![1, 2, 3, 4]);
+        let db = dbs.try_const().unwrap();
+        let t = thread::spawn(move || {
+            let db1 = db.slice(0, 2);
+            drop(db);
+            assert_eq!(db1, [1, 2][..]);
+        });
+        if let Ok(mut dbm) = dbs.try_mut() {
+            dbm[0] = 5;
+        }
+        t.join().unwrap();
+        // No decrements may be lost
+        assert!(dbs.try_mut().is_ok());
+        let v: Vec<u8> = dbs.try_into().unwrap();
+        assert_eq!(v.len(), 4);
+    });
+}
+
+/// Two readers and a writer race to access a buffer through
+/// `DivBufInaccessible`s while the owner drops its `DivBufShared`.  Any leak
+/// or double free of the storage will be caught by loom.
+#[test]
+fn readers_writer_owner() {
+    let mut builder = loom::model::Builder::new();
+    // An unbounded search would take far too long
+    if builder.preemption_bound.is_none() {
+        builder.preemption_bound = Some(2);
+    }
+    builder.check(|| {
+        let dbs = DivBufShared::from(vec![0; 4]);
+        let dbi0 = dbs.try_const().unwrap().clone_inaccessible();
+        let dbi1 = dbi0.clone();
+        let dbi2 = dbi0.clone();
+        let reader = thread::spawn(move || {
+            if let Ok(db) = dbi1.try_const() {
+                assert_eq!(db.len(), 4);
+            }
+        });
+        let writer = thread::spawn(move || {
+            if let Ok(mut dbm) = dbi2.try_mut() {
+                dbm[0] = 1;
+            }
+        });
+        // The owner's thread is also the second reader
+        let db = dbi0.try_const();
+        drop(dbs);
+        drop(dbi0);
+        if let Ok(db) = db {
+            assert_eq!(db.len(), 4);
+        }
+        reader.join().unwrap();
+        writer.join().unwrap();
+    });
+}